@@ -0,0 +1,80 @@
+use proc_macro::TokenStream;
+use proc_macro2::TokenStream as TokenStream2;
+use quote::quote;
+use syn::{Data, DeriveInput, Error, Fields, LitChar, parse_macro_input};
+
+// `#[derive(GridCell)]` on a fieldless enum with a `#[symbol('c')]` on every variant.
+// Implements `aoc2024::grid::CellSymbol` and adds an `ALL` list of the variants in
+// declaration order.
+#[proc_macro_derive(GridCell, attributes(symbol))]
+pub fn derive_grid_cell(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    expand(input)
+        .unwrap_or_else(Error::into_compile_error)
+        .into()
+}
+
+fn expand(input: DeriveInput) -> syn::Result<TokenStream2> {
+    let Data::Enum(data) = &input.data else {
+        return Err(Error::new_spanned(
+            &input.ident,
+            "GridCell can only be derived for enums",
+        ));
+    };
+
+    let mut variants = Vec::new();
+    let mut symbols: Vec<LitChar> = Vec::new();
+    for variant in &data.variants {
+        if !matches!(variant.fields, Fields::Unit) {
+            return Err(Error::new_spanned(
+                variant,
+                "GridCell variants cannot have fields",
+            ));
+        }
+
+        let mut attrs = variant.attrs.iter().filter(|a| a.path().is_ident("symbol"));
+        let Some(attr) = attrs.next() else {
+            return Err(Error::new_spanned(
+                variant,
+                "missing #[symbol('c')] attribute",
+            ));
+        };
+        if let Some(extra) = attrs.next() {
+            return Err(Error::new_spanned(extra, "duplicate #[symbol] attribute"));
+        }
+
+        let symbol: LitChar = attr.parse_args()?;
+        if let Some(prev) = symbols.iter().find(|s| s.value() == symbol.value()) {
+            let mut err = Error::new_spanned(&symbol, "symbol is used by another variant");
+            err.combine(Error::new_spanned(prev, "first used here"));
+            return Err(err);
+        }
+
+        variants.push(&variant.ident);
+        symbols.push(symbol);
+    }
+
+    let name = &input.ident;
+    let vis = &input.vis;
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+    Ok(quote! {
+        impl #impl_generics ::aoc2024::grid::CellSymbol for #name #ty_generics #where_clause {
+            fn from_symbol(symbol: char) -> ::core::result::Result<Self, ::aoc2024::grid::UnknownSymbol> {
+                match symbol {
+                    #( #symbols => ::core::result::Result::Ok(Self::#variants), )*
+                    c => ::core::result::Result::Err(::aoc2024::grid::UnknownSymbol::new(c)),
+                }
+            }
+
+            fn symbol(&self) -> char {
+                match self {
+                    #( Self::#variants => #symbols, )*
+                }
+            }
+        }
+
+        impl #impl_generics #name #ty_generics #where_clause {
+            #vis const ALL: &'static [Self] = &[#( Self::#variants ),*];
+        }
+    })
+}
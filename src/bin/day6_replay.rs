@@ -0,0 +1,43 @@
+// Replays the day 6 guard walk in the terminal, one leg per frame.
+//
+//   cargo run --release --bin day6_replay [test] [loops | ROW COL]
+//
+// `loops` shows the final loop for every obstruction that causes one, `ROW COL` the walk
+// with an obstruction added at that cell.
+use std::time::Duration;
+
+use aoc2024::day::day6::Puzzle;
+use aoc2024::grid::{GridIdx, Recorder};
+
+fn main() -> std::io::Result<()> {
+    let mut args: Vec<String> = std::env::args().skip(1).collect();
+    let pz = match args.first().map(String::as_str) {
+        Some("test") => {
+            args.remove(0);
+            Puzzle::new_test()
+        }
+        _ => Puzzle::new(),
+    };
+
+    let recorder = match args.as_slice() {
+        [] => pz.record_walk(),
+        [loops] if loops == "loops" => {
+            let mut recorder = Recorder::new();
+            for idx in pz.cycle_candidates() {
+                let cycle = pz.record_cycle(idx).unwrap();
+                let mut last = cycle.frames.last().unwrap().clone();
+                last.caption = format!("obstruction at {idx:?}, {}", last.caption);
+                recorder.frames.push(last);
+            }
+            recorder
+        }
+        [row, col] => {
+            let idx = GridIdx(row.parse().unwrap(), col.parse().unwrap());
+            pz.record_cycle(idx)
+                .expect("obstructions can only be added to open cells")
+        }
+        _ => panic!("usage: day6_replay [test] [loops | ROW COL]"),
+    };
+
+    recorder.play(Duration::from_millis(250))
+}
@@ -1,6 +1,6 @@
-use itertools::Itertools;
 use strum::IntoEnumIterator;
 
+use crate::grid::search::*;
 use crate::grid::*;
 
 const TEST_INPUT: &'static str = include_str!("../../puzzles/day4_test.txt");
@@ -19,68 +19,27 @@ impl Puzzle {
     }
 
     pub fn part2(&self) -> usize {
-        const MATCH_START: char = 'A';
-        const CASE1: [Option<char>; 2] = [Some('M'), Some('S')];
-        const CASE2: [Option<char>; 2] = [Some('S'), Some('M')];
-
-        use rayon::prelude::*;
-
-        self.0
-            .cells
-            .par_iter()
-            .positions(|c| c == &MATCH_START)
-            .map(|idx| self.0.grid_idx(idx).unwrap())
-            .filter(|rc| {
-                const D1: GridOffset = GridOffset(1, 1);
-                let diag1 = [self.0.get(*rc - D1).copied(), self.0.get(*rc + D1).copied()];
-
-                const D2: GridOffset = GridOffset(-1, 1);
-                let diag2 = [self.0.get(*rc - D2).copied(), self.0.get(*rc + D2).copied()];
-
-                (diag1 == CASE1 || diag1 == CASE2) && (diag2 == CASE1 || diag2 == CASE2)
-            })
-            .count()
+        const X_MAS: &str = "
+            M.S
+            .A.
+            M.S
+        ";
+
+        let pattern = parse_pattern(X_MAS, '.');
+        self.0.find_pattern_with(&pattern, Symmetry::iter()).len()
     }
 
-    pub fn find_all(&self, needle: &str) -> impl Iterator<Item = isize> {
-        let match_start = needle.chars().next().expect("needle should not be empty");
-
+    pub fn find_all(&self, needle: &str) -> Vec<WordMatch> {
+        let needle: Vec<char> = needle.chars().collect();
         self.0
-            .cells
-            .iter()
-            .positions(move |c| c == &match_start)
-            .map(|idx| {
-                let rc = self.0.grid_idx(idx).unwrap();
-                Adjacent::iter()
-                    .map(GridOffset::from)
-                    .map(|dir| self.counting_search(needle, rc, dir))
-                    .sum()
-            })
+            .find_words(&needle, Connectivity::Eight.offsets(), Overlap::Allow)
     }
 
-    fn counting_search(&self, needle: &str, rc: GridIdx, dir: GridOffset) -> isize {
-        if needle.is_empty() {
-            return 1; // needle exhausted/found
-        }
-
-        let (match_char, needle) = split_first_char(needle);
-        if match_char == self.0.get(rc).copied() {
-            self.counting_search(needle, rc + dir, dir)
-        } else {
-            0
-        }
-    }
-
-    pub fn part1(&self) -> isize {
-        self.find_all("XMAS").sum()
+    pub fn part1(&self) -> usize {
+        self.find_all("XMAS").len()
     }
 }
 
-fn split_first_char(needle: &str) -> (Option<char>, &str) {
-    let (head, tail) = needle.split_at(1);
-    (head.chars().next(), tail)
-}
-
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -1,25 +1,26 @@
-use std::collections::HashSet;
+use std::collections::HashMap;
+use std::error::Error;
+use std::fmt;
 
 use itertools::Itertools;
 use rayon::prelude::*;
 
 use crate::grid::*;
 
-#[derive(Debug, Copy, Clone, PartialEq)]
+#[derive(Debug, Copy, Clone, PartialEq, GridCell)]
 enum Cell {
+    #[symbol('#')]
     Obstruction,
+    #[symbol('.')]
     Open,
+    #[symbol('^')]
     Start,
 }
 
 fn rotate_right(offset: GridOffset) -> GridOffset {
-    let row = 0 * offset.0 + 1 * offset.1;
-    let col = -1 * offset.0 + 0 * offset.1;
-    GridOffset(row, col)
+    GridOffset(offset.col(), -offset.row())
 }
 
-type Turn = (GridIdx, GridOffset);
-
 #[derive(Debug, Clone)]
 pub struct Puzzle {
     grid: Grid<Cell>,
@@ -29,7 +30,7 @@ pub struct Puzzle {
 impl Puzzle {
     pub fn part1(&self) -> usize {
         let direction = GridOffset(-1, 0); // UP
-        let mut visited: HashSet<GridIdx> = HashSet::new();
+        let mut visited = self.visited();
         // iterator in a direction until:
         // - we walk out of bounds (iter returns None)
         //   - return the total unique cells visited
@@ -38,41 +39,62 @@ impl Puzzle {
         self.walk(self.start, direction, &mut visited)
     }
 
-    pub fn walk(&self, start: GridIdx, dir: GridOffset, visited: &mut HashSet<GridIdx>) -> usize {
-        let mut location = start;
-        for (idx, cell) in self.grid.iter_from_with_stride(start, dir) {
-            if cell == &Cell::Obstruction {
-                return self.walk(location, rotate_right(dir), visited);
-            } else {
-                location = idx;
-                visited.insert(location);
-            }
-        }
-        visited.len()
+    fn visited(&self) -> BitGrid {
+        BitGrid::new(self.grid.rows, self.grid.cols)
     }
 
-    pub fn has_cycles(&self) -> bool {
-        let mut path = HashSet::new();
-        self.inner_has_cycles(self.start, GridOffset(-1, 0), &mut path)
+    fn cast(&self, start: GridIdx, dir: GridOffset) -> Ray {
+        self.grid
+            .cast(start, dir, |cell| cell == &Cell::Obstruction)
     }
 
-    fn inner_has_cycles(&self, start: GridIdx, dir: GridOffset, path: &mut HashSet<Turn>) -> bool {
-        let turn = (start, dir);
-        if path.contains(&turn) {
-            return true; // we've already hit this cell moving in this direction so must be in a cycle.
-        }
-
-        path.insert(turn);
+    // Each straight run of the guard from `start`, turning right at every obstruction, up to
+    // the one leaving the map. Never ends if the guard loops.
+    fn legs(&self, start: GridIdx, dir: GridOffset) -> impl Iterator<Item = Ray> {
+        let mut next = Some((start, dir));
+        std::iter::from_fn(move || {
+            let (pos, dir) = next?;
+            let ray = self.cast(pos, dir);
+            next = ray.hit().map(|_| (ray.last(), rotate_right(dir)));
+            Some(ray)
+        })
+    }
 
-        let mut location = start;
-        for (idx, cell) in self.grid.iter_from_with_stride(start, dir) {
-            if cell == &Cell::Obstruction {
-                return self.inner_has_cycles(location, rotate_right(dir), path);
-            } else {
-                location = idx;
+    pub fn walk(&self, start: GridIdx, dir: GridOffset, visited: &mut BitGrid) -> usize {
+        for ray in self.legs(start, dir) {
+            for idx in ray.path() {
+                visited.insert(idx);
             }
         }
-        false
+        visited.count()
+    }
+
+    // the guard's turning points, from the start to the last cell before leaving the map
+    pub fn route(&self) -> Vec<GridIdx> {
+        let turns = self
+            .legs(self.start, GridOffset(-1, 0))
+            .map(|ray| ray.last());
+        std::iter::once(self.start).chain(turns).collect()
+    }
+
+    pub fn picture(&self) -> Picture {
+        let mut visited = self.visited();
+        self.walk(self.start, GridOffset(-1, 0), &mut visited);
+        self.grid
+            .picture(|cell| match cell {
+                Cell::Obstruction => Rgb::BLACK,
+                Cell::Open | Cell::Start => Rgb::WHITE,
+            })
+            .cells(visited.iter(), Rgb::YELLOW)
+            .path(self.route(), Rgb::RED)
+            .cells([self.start], Rgb::BLUE)
+    }
+
+    pub fn has_cycles(&self) -> bool {
+        let mut path = DirBitGrid::new(self.grid.rows, self.grid.cols);
+        // a leg starting where an earlier one did, heading the same way, means a cycle
+        self.legs(self.start, GridOffset(-1, 0))
+            .any(|ray| !path.insert(ray.start, ray.dir))
     }
 
     // filter locations where a new obstruction would cause a cycle
@@ -90,16 +112,87 @@ impl Puzzle {
 
     // 3 seconds
     pub fn part2_only_visited(&self) -> usize {
-        let mut visited = HashSet::new();
+        self.cycle_candidates().len()
+    }
+
+    // cells on the guard's path where an obstruction makes them loop
+    pub fn cycle_candidates(&self) -> Vec<GridIdx> {
+        let mut visited = self.visited();
         self.walk(self.start, GridOffset(-1, 0), &mut visited);
-        self.filter_cycles(visited.into_iter()).count()
+        self.filter_cycles(visited.iter()).collect()
+    }
+
+    pub fn record_walk(&self) -> Recorder {
+        let mut recorder = Recorder::new();
+        self.trace(&mut recorder, None);
+        recorder
+    }
+
+    // the walk with an extra obstruction at `idx`, `None` where one cannot be placed
+    pub fn record_cycle(&self, idx: GridIdx) -> Option<Recorder> {
+        if self.grid.get(idx) != Some(&Cell::Open) {
+            return None;
+        }
+
+        let mut case = self.clone();
+        case.grid.put(idx, Cell::Obstruction);
+        let mut recorder = Recorder::new();
+        case.trace(&mut recorder, Some(idx));
+        Some(recorder)
+    }
+
+    // One frame per leg of the walk: the trail so far, the guard as an arrow and any added
+    // obstruction as `O`. True if the guard ends up in a loop.
+    fn trace(&self, recorder: &mut Recorder, added: Option<GridIdx>) -> bool {
+        let mut path = DirBitGrid::new(self.grid.rows, self.grid.cols);
+        let mut trail: HashMap<GridIdx, char> = HashMap::new();
+        let mut record = |trail: &HashMap<GridIdx, char>, pos: GridIdx, dir, caption: String| {
+            let guard = match dir {
+                GridOffset(-1, 0) => '^',
+                GridOffset(0, 1) => '>',
+                GridOffset(1, 0) => 'v',
+                _ => '<',
+            };
+            let marks = trail.iter().map(|(idx, mark)| (*idx, *mark));
+            let marks = marks
+                .chain(added.map(|idx| (idx, 'O')))
+                .chain([(pos, guard)]);
+            recorder.record(&self.grid, marks, caption);
+        };
+
+        let mut leg = 0;
+        for ray in self.legs(self.start, GridOffset(-1, 0)) {
+            leg += 1;
+            let Ray { start, dir, .. } = ray;
+            if !path.insert(start, dir) {
+                record(&trail, start, dir, format!("leg {leg}: loop at {start:?}"));
+                return true;
+            }
+
+            let mark = if dir.row() == 0 { '-' } else { '|' };
+            for idx in ray.path() {
+                trail
+                    .entry(idx)
+                    .and_modify(|prev| *prev = if *prev == mark { mark } else { '+' })
+                    .or_insert(mark);
+            }
+
+            let caption = match ray.hit() {
+                Some(hit) => format!("leg {leg}: blocked at {hit:?}"),
+                None => format!("leg {leg}: left the map"),
+            };
+            record(&trail, ray.last(), dir, caption);
+        }
+        false
     }
 
     // 1.5 seconds (0.090 --release)
     pub fn part2_parallel(&self) -> usize {
-        let mut visited = HashSet::new();
+        let mut visited = self.visited();
         self.walk(self.start, GridOffset(-1, 0), &mut visited);
         visited
+            .iter()
+            .collect::<Vec<_>>()
             .into_par_iter()
             .filter(|idx| self.introduces_cycle(*idx))
             .count()
@@ -120,15 +213,15 @@ impl Puzzle {
 
     pub fn part2_parallel2(&self) -> usize {
         use std::thread;
-        let mut visited = HashSet::new();
+        let mut visited = self.visited();
         self.walk(self.start, GridOffset(-1, 0), &mut visited);
 
         let n = num_cpus::get();
-        let chunk_size = visited.len() / n;
+        let chunk_size = visited.count() / n;
         println!("{n} threads of {chunk_size}");
 
         let chunked = visited
-            .into_iter()
+            .iter()
             .chunks(chunk_size)
             .into_iter()
             .map(|x| x.collect::<Vec<_>>())
@@ -151,6 +244,29 @@ impl Puzzle {
     }
 }
 
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum ParseError {
+    Grid(GridParseError),
+    NoGuard,
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ParseError::Grid(err) => err.fmt(f),
+            ParseError::NoGuard => write!(f, "no guard on the map"),
+        }
+    }
+}
+
+impl Error for ParseError {}
+
+impl From<GridParseError> for ParseError {
+    fn from(value: GridParseError) -> Self {
+        ParseError::Grid(value)
+    }
+}
+
 mod input {
     use super::*;
 
@@ -159,27 +275,24 @@ mod input {
 
     impl Puzzle {
         pub fn new_test() -> Self {
-            Self::parse(TEST_INPUT)
+            Self::parse(TEST_INPUT).unwrap()
         }
 
         pub fn new() -> Self {
-            Self::parse(INPUT)
+            Self::parse(INPUT).unwrap()
         }
 
-        pub fn parse(input: &str) -> Self {
-            let grid = parse_grid(input);
-            let start = grid.position(|c| c == &Cell::Start).unwrap();
-            Puzzle { grid, start }
+        pub fn parse(input: &str) -> Result<Self, ParseError> {
+            let grid = parse_grid(input)?;
+            let start = grid
+                .position(|c| c == &Cell::Start)
+                .ok_or(ParseError::NoGuard)?;
+            Ok(Puzzle { grid, start })
         }
     }
 
-    pub fn parse_grid(input: &str) -> Grid<Cell> {
-        Grid::parse(input, |c| match c {
-            '.' => Cell::Open,
-            '#' => Cell::Obstruction,
-            '^' => Cell::Start,
-            e => panic!("bad input: {:?}", e),
-        })
+    pub fn parse_grid(input: &str) -> Result<Grid<Cell>, GridParseError> {
+        Grid::parse_symbols(input)
     }
 }
 
@@ -189,8 +302,48 @@ mod test {
 
     #[test]
     fn test_parse() {
-        let grid = input::parse_grid(input::TEST_INPUT);
+        let grid = input::parse_grid(input::TEST_INPUT).unwrap();
         assert_eq!(grid.size(), (10, 10));
+        assert_eq!(grid.to_string(), input::TEST_INPUT.trim());
+        assert!(input::parse_grid("..\n.X").is_err());
+        let err = Puzzle::parse("^.\n.X").unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "unknown cell symbol 'X' at row 1, column 1"
+        );
+        assert_eq!(Puzzle::parse("..\n.#").unwrap_err(), ParseError::NoGuard);
+        assert!(matches!(Puzzle::parse("\n"), Err(ParseError::Grid(_))));
+    }
+
+    #[test]
+    fn test_route() {
+        let pz = Puzzle::new_test();
+        let route = pz.route();
+        assert_eq!(route.first(), Some(&GridIdx(6, 4)));
+        assert_eq!(route.last(), Some(&GridIdx(9, 7)));
+
+        let picture = pz.picture().with_scale(4);
+        assert_eq!(picture.size(), (40, 40));
+        let raster = picture.raster();
+        assert_eq!(raster.count(|c| *c == Rgb::BLACK), 8);
+        assert_eq!(raster.count(|c| *c != Rgb::WHITE && *c != Rgb::BLACK), 41);
+    }
+
+    #[test]
+    fn test_record() {
+        let pz = Puzzle::new_test();
+        let walk = pz.record_walk();
+        assert_eq!(walk.len(), pz.route().len() - 1);
+        assert_eq!(walk.frames.last().unwrap().caption, "leg 11: left the map");
+
+        // the first candidate from the puzzle description
+        let cycle = pz.record_cycle(GridIdx(6, 3)).unwrap();
+        let last = cycle.frames.last().unwrap();
+        assert!(last.caption.ends_with("loop at GridIdx(6, 4)"));
+        assert_eq!(last.cells.get(GridIdx(6, 3)), Some(&'O'));
+        assert_eq!(last.cells.get(GridIdx(6, 4)), Some(&'^'));
+        assert_eq!(last.cells.get(GridIdx(1, 8)), Some(&'+'));
+        assert!(pz.record_cycle(GridIdx(0, 4)).is_none());
     }
 
     #[test]
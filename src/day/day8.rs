@@ -1,70 +1,92 @@
-use std::collections::{HashMap, HashSet};
-
 use itertools::Itertools;
 
-use crate::grid::GridIdx;
+use crate::grid::{BitGrid, GridBounds, GridIdx, Picture, Rgb, SparseGrid, line};
 
 pub struct Puzzle {
-    cells: HashMap<char, Vec<GridIdx>>,
-    rows: usize,
-    cols: usize,
+    antennas: SparseGrid<char>,
 }
 
 impl Puzzle {
+    // the map's extent, declared on the antennas when parsing
+    fn bounds(&self) -> GridBounds {
+        self.antennas.bounds().unwrap()
+    }
+
     fn project(&self, a: GridIdx, b: GridIdx) -> Option<GridIdx> {
         let offset = a - b;
         let projected = a + offset;
-        let rows = 0..self.rows as isize;
-        let cols = 0..self.cols as isize;
-        if rows.contains(&projected.0) && cols.contains(&projected.1) {
-            Some(projected)
-        } else {
-            None
-        }
+        self.bounds().contains(projected).then_some(projected)
+    }
+
+    fn antinodes(&self) -> BitGrid {
+        let (rows, cols) = self.bounds().size();
+        BitGrid::new(rows, cols)
     }
 
     pub fn part1(&self) -> usize {
-        let mut reflections: HashSet<Option<GridIdx>> = HashSet::new();
-        reflections.insert(None);
+        let mut reflections = self.antinodes();
 
-        for (_, nodes) in self.cells.iter() {
+        for nodes in self.antennas.groups().values() {
             for (a, b) in nodes.iter().tuple_combinations() {
-                reflections.insert(self.project(*a, *b));
-                reflections.insert(self.project(*b, *a));
+                for projected in [self.project(*a, *b), self.project(*b, *a)]
+                    .into_iter()
+                    .flatten()
+                {
+                    reflections.insert(projected);
+                }
             }
         }
 
-        reflections.len() - 1
+        reflections.count()
     }
 
     pub fn part2(&self) -> usize {
-        let mut reflections: HashSet<Option<GridIdx>> = HashSet::new();
-        reflections.insert(None);
+        self.resonant_antinodes().count()
+    }
 
-        for (_, nodes) in self.cells.iter() {
+    fn resonant_antinodes(&self) -> BitGrid {
+        let mut reflections = self.antinodes();
+
+        for nodes in self.antennas.groups().values() {
             for (a, b) in nodes.iter().tuple_combinations() {
                 self.accum_towards(&mut reflections, *a, *b);
                 self.accum_towards(&mut reflections, *b, *a);
             }
         }
 
-        reflections.len() - 1
+        reflections
     }
 
-    pub fn accum_towards(&self, set: &mut HashSet<Option<GridIdx>>, a: GridIdx, mut b: GridIdx) {
-        let rows = 0..self.rows as isize;
-        let cols = 0..self.cols as isize;
+    // each frequency's lines in its own color, with part 2 antinodes and the antennas on top
+    pub fn picture(&self) -> Picture {
+        let groups = self.antennas.groups();
+        let bounds = self.bounds();
+        let mut picture = self.antennas.to_grid('.').picture(|_| Rgb::WHITE);
+
+        for (n, freq) in groups.keys().sorted().enumerate() {
+            for (a, b) in groups[freq].iter().tuple_combinations() {
+                let line: Vec<_> = line::line_within(*a, *b, bounds).collect();
+                picture = picture.line(line[0], line[line.len() - 1], Rgb::categorical(n));
+            }
+        }
 
-        let step = b - a;
-        while rows.contains(&b.0) && cols.contains(&b.1) {
-            set.insert(Some(b));
-            b = b + step;
+        picture
+            .cells(self.resonant_antinodes().iter(), Rgb::GRAY)
+            .cells(self.antennas.iter().map(|(idx, _)| idx), Rgb::BLACK)
+    }
+
+    pub fn accum_towards(&self, set: &mut BitGrid, a: GridIdx, b: GridIdx) {
+        let step = (b - a).gcd_normalize();
+        let bounds = self.bounds();
+        for idx in line::ray_within(a, step, |idx| bounds.contains(idx)) {
+            set.insert(idx);
         }
     }
 }
 
 mod input {
     use super::*;
+    use crate::grid::parse_char_grid;
 
     const TEST_INPUT: &str = include_str!("../../puzzles/day8_test.txt");
     const INPUT: &str = include_str!("../../puzzles/day8.txt");
@@ -79,23 +101,9 @@ mod input {
         }
 
         pub fn parse(input: &str) -> Self {
-            let mut cells: HashMap<char, Vec<GridIdx>> = HashMap::new();
-            let mut lines = input.lines().filter(|x| !x.is_empty()).peekable();
-            let cols = lines.peek().unwrap().len();
-            let mut rows = 0;
-            for (row, line) in lines.enumerate() {
-                for (col, token) in line.chars().enumerate() {
-                    if token == '.' {
-                        continue;
-                    }
-
-                    let idx = GridIdx::new(row, col);
-                    cells.entry(token).or_insert(Vec::new()).push(idx);
-                }
-                rows += 1;
+            Self {
+                antennas: parse_char_grid(input).to_sparse(|c| *c != '.'),
             }
-
-            Self { cells, rows, cols }
         }
     }
 }
@@ -121,4 +129,13 @@ mod tests {
         let pz = Puzzle::new();
         assert_eq!(pz.part2(), 1150);
     }
+
+    #[test]
+    fn test_picture() {
+        let pz = Puzzle::new_test();
+        let raster = pz.picture().raster();
+        assert_eq!(raster.size(), (12, 12));
+        assert_eq!(raster.count(|c| *c == Rgb::BLACK), 7);
+        assert_eq!(raster.count(|c| *c == Rgb::GRAY), 34 - 7);
+    }
 }
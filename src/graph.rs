@@ -0,0 +1,348 @@
+use std::cmp::Ordering;
+use std::collections::{BinaryHeap, HashMap, HashSet, VecDeque};
+use std::hash::Hash;
+use std::ops::Add;
+
+use crate::grid::{Connectivity, Grid, GridIdx};
+
+// Searches are generic over a state `S` and a successor function. Weighted searches take
+// successors as `(state, step cost)` pairs and treat `C::default()` as zero cost.
+// Paths include both the start and the goal state.
+
+pub fn bfs<S, I>(
+    start: S,
+    mut successors: impl FnMut(&S) -> I,
+    mut goal: impl FnMut(&S) -> bool,
+) -> Option<Vec<S>>
+where
+    S: Clone + Eq + Hash,
+    I: IntoIterator<Item = S>,
+{
+    let mut parents: HashMap<S, S> = HashMap::new();
+    let mut seen = HashSet::from([start.clone()]);
+    let mut queue = VecDeque::from([start]);
+    while let Some(state) = queue.pop_front() {
+        if goal(&state) {
+            return Some(reconstruct(&parents, state));
+        }
+
+        for next in successors(&state) {
+            if seen.insert(next.clone()) {
+                parents.insert(next.clone(), state.clone());
+                queue.push_back(next);
+            }
+        }
+    }
+    None
+}
+
+// number of steps to every state reachable from `start`
+pub fn bfs_distances<S, I>(start: S, mut successors: impl FnMut(&S) -> I) -> HashMap<S, usize>
+where
+    S: Clone + Eq + Hash,
+    I: IntoIterator<Item = S>,
+{
+    let mut distances = HashMap::from([(start.clone(), 0)]);
+    let mut queue = VecDeque::from([(start, 0)]);
+    while let Some((state, steps)) = queue.pop_front() {
+        for next in successors(&state) {
+            if !distances.contains_key(&next) {
+                distances.insert(next.clone(), steps + 1);
+                queue.push_back((next, steps + 1));
+            }
+        }
+    }
+    distances
+}
+
+// finds a path, not necessarily the shortest one
+pub fn dfs<S, I>(
+    start: S,
+    mut successors: impl FnMut(&S) -> I,
+    mut goal: impl FnMut(&S) -> bool,
+) -> Option<Vec<S>>
+where
+    S: Clone + Eq + Hash,
+    I: IntoIterator<Item = S>,
+{
+    let mut parents: HashMap<S, S> = HashMap::new();
+    let mut visited = HashSet::new();
+    let mut stack = vec![(start, None)];
+    while let Some((state, parent)) = stack.pop() {
+        if !visited.insert(state.clone()) {
+            continue;
+        }
+
+        if let Some(parent) = parent {
+            parents.insert(state.clone(), parent);
+        }
+
+        if goal(&state) {
+            return Some(reconstruct(&parents, state));
+        }
+
+        for next in successors(&state) {
+            if !visited.contains(&next) {
+                stack.push((next, Some(state.clone())));
+            }
+        }
+    }
+    None
+}
+
+pub fn dijkstra<S, C, I>(
+    start: S,
+    successors: impl FnMut(&S) -> I,
+    goal: impl FnMut(&S) -> bool,
+) -> Option<(Vec<S>, C)>
+where
+    S: Clone + Eq + Hash,
+    C: Copy + Ord + Default + Add<Output = C>,
+    I: IntoIterator<Item = (S, C)>,
+{
+    astar(start, successors, |_| C::default(), goal)
+}
+
+// `heuristic` must never overestimate the remaining cost or the path may not be the cheapest
+pub fn astar<S, C, I>(
+    start: S,
+    mut successors: impl FnMut(&S) -> I,
+    mut heuristic: impl FnMut(&S) -> C,
+    mut goal: impl FnMut(&S) -> bool,
+) -> Option<(Vec<S>, C)>
+where
+    S: Clone + Eq + Hash,
+    C: Copy + Ord + Default + Add<Output = C>,
+    I: IntoIterator<Item = (S, C)>,
+{
+    let mut parents: HashMap<S, S> = HashMap::new();
+    let mut best = HashMap::from([(start.clone(), C::default())]);
+    let mut frontier = BinaryHeap::from([Frontier {
+        priority: heuristic(&start),
+        cost: C::default(),
+        state: start,
+    }]);
+
+    while let Some(Frontier { cost, state, .. }) = frontier.pop() {
+        if best.get(&state).is_some_and(|best| *best < cost) {
+            continue; // stale entry, a cheaper route was already expanded
+        }
+
+        if goal(&state) {
+            return Some((reconstruct(&parents, state), cost));
+        }
+
+        for (next, step) in successors(&state) {
+            let cost = cost + step;
+            if best.get(&next).is_none_or(|best| cost < *best) {
+                best.insert(next.clone(), cost);
+                parents.insert(next.clone(), state.clone());
+                frontier.push(Frontier {
+                    priority: cost + heuristic(&next),
+                    cost,
+                    state: next,
+                });
+            }
+        }
+    }
+    None
+}
+
+fn reconstruct<S: Clone + Eq + Hash>(parents: &HashMap<S, S>, goal: S) -> Vec<S> {
+    let mut path = vec![goal];
+    while let Some(parent) = parents.get(path.last().unwrap()) {
+        path.push(parent.clone());
+    }
+    path.reverse();
+    path
+}
+
+// min-heap entry ordered by priority alone
+struct Frontier<S, C> {
+    priority: C,
+    cost: C,
+    state: S,
+}
+
+impl<S, C: Ord> PartialEq for Frontier<S, C> {
+    fn eq(&self, other: &Self) -> bool {
+        self.priority == other.priority
+    }
+}
+
+impl<S, C: Ord> Eq for Frontier<S, C> {}
+
+impl<S, C: Ord> PartialOrd for Frontier<S, C> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<S, C: Ord> Ord for Frontier<S, C> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        other.priority.cmp(&self.priority)
+    }
+}
+
+// Grid adapters. Moves are orthogonal steps; `cost` is the price of entering a cell and
+// `None` marks it impassable. Paths stay on the stored cells: they wrap across the edges of
+// a wrapping grid but never leave an infinite one.
+impl<T> Grid<T> {
+    fn steps(&self, index: GridIdx) -> impl Iterator<Item = (GridIdx, &T)> {
+        let bounds = self.bounds();
+        self.neighbors(index, Connectivity::Four)
+            .filter(move |(next, _)| bounds.contains(*next))
+    }
+
+    fn open_steps(&self, index: GridIdx, passable: &impl Fn(&T) -> bool) -> Vec<GridIdx> {
+        self.steps(index)
+            .filter(|(_, cell)| passable(cell))
+            .map(|(next, _)| next)
+            .collect()
+    }
+
+    pub fn bfs_path(
+        &self,
+        start: GridIdx,
+        goal: GridIdx,
+        passable: impl Fn(&T) -> bool,
+    ) -> Option<Vec<GridIdx>> {
+        let successors = |idx: &GridIdx| self.open_steps(*idx, &passable);
+        bfs(start, successors, |idx| *idx == goal)
+    }
+
+    // steps from `start` to every reachable cell, `None` where unreachable
+    pub fn distance_map(
+        &self,
+        start: GridIdx,
+        passable: impl Fn(&T) -> bool,
+    ) -> Grid<Option<usize>> {
+        let successors = |idx: &GridIdx| self.open_steps(*idx, &passable);
+        let distances = bfs_distances(start, successors);
+        Grid::from_fn(self.rows, self.cols, |idx| distances.get(&idx).copied())
+    }
+
+    pub fn dijkstra_path<C>(
+        &self,
+        start: GridIdx,
+        goal: GridIdx,
+        cost: impl Fn(&T) -> Option<C>,
+    ) -> Option<(Vec<GridIdx>, C)>
+    where
+        C: Copy + Ord + Default + Add<Output = C>,
+    {
+        self.astar_path(start, goal, cost, |_| C::default())
+    }
+
+    pub fn astar_path<C>(
+        &self,
+        start: GridIdx,
+        goal: GridIdx,
+        cost: impl Fn(&T) -> Option<C>,
+        heuristic: impl Fn(GridIdx) -> C,
+    ) -> Option<(Vec<GridIdx>, C)>
+    where
+        C: Copy + Ord + Default + Add<Output = C>,
+    {
+        let successors = |idx: &GridIdx| {
+            self.steps(*idx)
+                .filter_map(|(next, cell)| Some((next, cost(cell)?)))
+                .collect::<Vec<_>>()
+        };
+        astar(start, successors, |idx| heuristic(*idx), |idx| *idx == goal)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::grid::{Topology, parse_char_grid};
+
+    const MAZE: &str = "
+        S.#.....
+        .##.###.
+        ...#...#
+        .#...#.E
+    ";
+
+    // edges of a small weighted digraph: 0 -> 1 -> 3 is cheap, 0 -> 3 is direct but costly
+    fn edges(node: &u32) -> Vec<(u32, u32)> {
+        match node {
+            0 => vec![(1, 1), (2, 4), (3, 10)],
+            1 => vec![(3, 2)],
+            2 => vec![(3, 1)],
+            _ => vec![],
+        }
+    }
+
+    #[test]
+    fn test_bfs() {
+        let path = bfs(0, |n| edges(n).into_iter().map(|e| e.0), |n| *n == 3);
+        assert_eq!(path, Some(vec![0, 3]));
+        assert_eq!(
+            bfs(1, |n| edges(n).into_iter().map(|e| e.0), |n| *n == 0),
+            None
+        );
+
+        let distances = bfs_distances(0, |n| edges(n).into_iter().map(|e| e.0));
+        assert_eq!(distances.len(), 4);
+        assert_eq!(distances[&3], 1);
+    }
+
+    #[test]
+    fn test_dfs() {
+        let path = dfs(0, |n| edges(n).into_iter().map(|e| e.0), |n| *n == 3).unwrap();
+        assert_eq!(path.first(), Some(&0));
+        assert_eq!(path.last(), Some(&3));
+        for (a, b) in path.iter().zip(path.iter().skip(1)) {
+            assert!(edges(a).iter().any(|e| e.0 == *b));
+        }
+    }
+
+    #[test]
+    fn test_dijkstra() {
+        let (path, cost) = dijkstra(0, edges, |n| *n == 3).unwrap();
+        assert_eq!(path, [0, 1, 3]);
+        assert_eq!(cost, 3);
+
+        let (path, cost) = dijkstra(2, edges, |n| *n == 2).unwrap();
+        assert_eq!(path, [2]);
+        assert_eq!(cost, 0);
+    }
+
+    #[test]
+    fn test_grid_paths() {
+        let grid = parse_char_grid(MAZE);
+        let start = grid.position(|c| *c == 'S').unwrap();
+        let goal = grid.position(|c| *c == 'E').unwrap();
+
+        let path = grid.bfs_path(start, goal, |c| *c != '#').unwrap();
+        assert_eq!(path.len(), 13);
+
+        let distances = grid.distance_map(start, |c| *c != '#');
+        assert_eq!(distances.get(goal), Some(&Some(12)));
+        assert_eq!(distances.get(GridIdx(0, 2)), Some(&None));
+
+        let cost = |c: &char| (*c != '#').then_some(1);
+        let (path, cost) = grid.dijkstra_path(start, goal, cost).unwrap();
+        assert_eq!((path.len(), cost), (13, 12));
+
+        let manhattan = |idx: GridIdx| goal.manhattan(idx) as u32;
+        let (path, cost) = grid
+            .astar_path(start, goal, |c| (*c != '#').then_some(1), manhattan)
+            .unwrap();
+        assert_eq!((path.len(), cost), (13, 12));
+    }
+
+    #[test]
+    fn test_infinite_grid_paths() {
+        // the way around is shorter through the neighboring tiles, which are off limits
+        let grid = parse_char_grid("S#E\n...").with_topology(Topology::Infinite);
+        let path = grid
+            .bfs_path(GridIdx(0, 0), GridIdx(0, 2), |c| *c != '#')
+            .unwrap();
+        assert_eq!(path.len(), 5);
+        assert!(path.iter().all(|idx| grid.bounds().contains(*idx)));
+        assert_eq!(grid.bfs_path(GridIdx(0, 0), GridIdx(0, 5), |_| true), None);
+    }
+}
@@ -0,0 +1,222 @@
+use std::collections::HashMap;
+use std::hash::Hash;
+
+use rayon::prelude::*;
+
+use super::*;
+
+// A grid stepped by a local rule, `rule(cell, neighbors) -> next cell`, with every cell
+// updated from the same generation. Neighbors follow the grid's topology, so edge cells
+// see fewer of them on a bounded grid.
+pub struct Automaton<T, F> {
+    current: Grid<T>,
+    next: Grid<T>,
+    connectivity: Connectivity,
+    rule: F,
+    parallel: bool,
+    generation: usize,
+}
+
+// The state at `start` comes back every `period` steps.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub struct Cycle {
+    pub start: usize,
+    pub period: usize,
+}
+
+impl<T, F> Automaton<T, F>
+where
+    T: Clone + Send + Sync,
+    F: Fn(&T, &[&T]) -> T + Sync,
+{
+    pub fn new(grid: Grid<T>, connectivity: Connectivity, rule: F) -> Self {
+        Self {
+            next: grid.clone(),
+            current: grid,
+            connectivity,
+            rule,
+            parallel: false,
+            generation: 0,
+        }
+    }
+
+    // compute rows on the rayon pool
+    pub fn with_parallel(mut self, parallel: bool) -> Self {
+        self.parallel = parallel;
+        self
+    }
+
+    pub fn grid(&self) -> &Grid<T> {
+        &self.current
+    }
+
+    pub fn into_grid(self) -> Grid<T> {
+        self.current
+    }
+
+    pub fn generation(&self) -> usize {
+        self.generation
+    }
+
+    pub fn step(&mut self) {
+        let Self {
+            current,
+            next,
+            connectivity,
+            rule,
+            ..
+        } = self;
+
+        let cols = current.cols.max(1);
+        let update_row = |(row, out): (usize, &mut [T])| {
+            let mut neighbors = Vec::with_capacity(8);
+            for (col, cell) in out.iter_mut().enumerate() {
+                let idx = GridIdx::new(row, col);
+                neighbors.clear();
+                neighbors.extend(current.neighbors(idx, *connectivity).map(|(_, n)| n));
+                *cell = rule(current.get(idx).unwrap(), &neighbors);
+            }
+        };
+
+        if self.parallel {
+            next.cells
+                .par_chunks_mut(cols)
+                .enumerate()
+                .for_each(update_row);
+        } else {
+            next.cells.chunks_mut(cols).enumerate().for_each(update_row);
+        }
+
+        std::mem::swap(current, next);
+        self.generation += 1;
+    }
+
+    pub fn steps(&mut self, n: usize) {
+        for _ in 0..n {
+            self.step();
+        }
+    }
+}
+
+impl<T, F> Automaton<T, F>
+where
+    T: Clone + Send + Sync + Eq + Hash,
+    F: Fn(&T, &[&T]) -> T + Sync,
+{
+    fn state(&self) -> Vec<T> {
+        self.current.cells.clone()
+    }
+
+    // Steps until a state repeats, leaving the automaton at `start + period`. Every state
+    // seen is kept for comparison. A finite grid always cycles, but possibly only after a
+    // long time.
+    pub fn find_cycle(&mut self) -> Cycle {
+        let mut seen = HashMap::from([(self.state(), self.generation)]);
+        loop {
+            self.step();
+            if let Some(start) = seen.insert(self.state(), self.generation) {
+                return Cycle {
+                    start,
+                    period: self.generation - start,
+                };
+            }
+        }
+    }
+
+    // Advances to `generation`, skipping whole cycles once one is found. The generation
+    // count then reads `generation` even though fewer steps were run.
+    pub fn fast_forward(&mut self, generation: usize) {
+        assert!(
+            generation >= self.generation,
+            "cannot rewind from {} to {generation}",
+            self.generation
+        );
+
+        let mut seen = HashMap::from([(self.state(), self.generation)]);
+        while self.generation < generation {
+            self.step();
+            if let Some(start) = seen.insert(self.state(), self.generation) {
+                let period = self.generation - start;
+                let remaining = (generation - self.generation) % period;
+                self.steps(remaining);
+                self.generation = generation;
+                return;
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // Conway's game of life on `#` and `.`
+    fn life(cell: &char, neighbors: &[&char]) -> char {
+        let alive = neighbors.iter().filter(|n| ***n == '#').count();
+        match (*cell, alive) {
+            ('#', 2 | 3) | ('.', 3) => '#',
+            _ => '.',
+        }
+    }
+
+    #[test]
+    fn test_blinker() {
+        let grid = parse_char_grid(".....\n..#..\n..#..\n..#..\n.....");
+        let mut life = Automaton::new(grid.clone(), Connectivity::Eight, life);
+        life.step();
+        assert_eq!(life.grid().to_string(), ".....\n.....\n.###.\n.....\n.....");
+        assert_eq!(life.generation(), 1);
+
+        let cycle = life.find_cycle();
+        assert_eq!(
+            cycle,
+            Cycle {
+                start: 1,
+                period: 2
+            }
+        );
+
+        life.fast_forward(1_000_000_000);
+        assert_eq!(life.generation(), 1_000_000_000);
+        assert_eq!(life.grid(), &grid);
+    }
+
+    #[test]
+    fn test_glider_on_torus() {
+        let grid = parse_char_grid(".#....\n..#...\n###...\n......\n......\n......")
+            .with_topology(Topology::Wrapping);
+        let mut glider = Automaton::new(grid.clone(), Connectivity::Eight, life);
+        // one cell diagonally every 4 generations
+        assert_eq!(
+            glider.find_cycle(),
+            Cycle {
+                start: 0,
+                period: 24
+            }
+        );
+        assert_eq!(glider.grid(), &grid);
+
+        glider.steps(4);
+        let moved: Vec<_> = glider.grid().positions(|c| *c == '#').collect();
+        let expected: Vec<_> = grid
+            .positions(|c| *c == '#')
+            .map(|idx| idx + GridOffset(1, 1))
+            .collect();
+        assert_eq!(moved, expected);
+    }
+
+    #[test]
+    fn test_parallel_matches_sequential() {
+        // a deterministic soup
+        let grid = Grid::from_fn(64, 64, |idx| {
+            let i = idx.row() * 64 + idx.col();
+            if (i * 7919) % 13 < 5 { '#' } else { '.' }
+        });
+
+        let mut sequential = Automaton::new(grid.clone(), Connectivity::Eight, life);
+        let mut parallel = Automaton::new(grid, Connectivity::Eight, life).with_parallel(true);
+        sequential.steps(20);
+        parallel.steps(20);
+        assert_eq!(sequential.into_grid(), parallel.into_grid());
+    }
+}
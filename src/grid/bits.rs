@@ -0,0 +1,294 @@
+use super::*;
+
+const WORD: usize = u64::BITS as usize;
+
+// A set of cells of a `rows` x `cols` grid, one bit per cell.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct BitGrid {
+    words: Vec<u64>,
+    rows: usize,
+    cols: usize,
+}
+
+impl BitGrid {
+    pub fn new(rows: usize, cols: usize) -> Self {
+        Self {
+            words: vec![0; (rows * cols).div_ceil(WORD)],
+            rows,
+            cols,
+        }
+    }
+
+    pub fn size(&self) -> (usize, usize) {
+        (self.rows, self.cols)
+    }
+
+    fn bit(&self, index: GridIdx) -> Option<(usize, u64)> {
+        if !GridBounds::from_size(self.rows, self.cols).contains(index) {
+            return None;
+        }
+
+        let flat = index.row() * self.cols + index.col();
+        Some((flat / WORD, 1 << (flat % WORD)))
+    }
+
+    // true if the cell was not already in the set; panics on out of bounds cells
+    pub fn insert(&mut self, index: GridIdx) -> bool {
+        let Some((word, mask)) = self.bit(index) else {
+            panic!("{index:?} is outside the {}x{} grid", self.rows, self.cols);
+        };
+
+        let fresh = self.words[word] & mask == 0;
+        self.words[word] |= mask;
+        fresh
+    }
+
+    pub fn remove(&mut self, index: GridIdx) -> bool {
+        let Some((word, mask)) = self.bit(index) else {
+            return false;
+        };
+
+        let present = self.words[word] & mask != 0;
+        self.words[word] &= !mask;
+        present
+    }
+
+    pub fn contains(&self, index: GridIdx) -> bool {
+        self.bit(index)
+            .is_some_and(|(word, mask)| self.words[word] & mask != 0)
+    }
+
+    pub fn count(&self) -> usize {
+        self.words.iter().map(|w| w.count_ones() as usize).sum()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.words.iter().all(|w| *w == 0)
+    }
+
+    pub fn clear(&mut self) {
+        self.words.fill(0);
+    }
+
+    // row-major order
+    pub fn iter(&self) -> impl Iterator<Item = GridIdx> {
+        self.words.iter().enumerate().flat_map(move |(i, word)| {
+            let mut word = *word;
+            std::iter::from_fn(move || {
+                if word == 0 {
+                    return None;
+                }
+                let flat = i * WORD + word.trailing_zeros() as usize;
+                word &= word - 1;
+                Some(GridIdx::new(flat / self.cols, flat % self.cols))
+            })
+        })
+    }
+
+    fn zip_words(&mut self, other: &Self, op: impl Fn(u64, u64) -> u64) {
+        assert_eq!(self.size(), other.size(), "bit grids must be the same size");
+        for (a, b) in self.words.iter_mut().zip(other.words.iter()) {
+            *a = op(*a, *b);
+        }
+    }
+
+    pub fn union_with(&mut self, other: &Self) {
+        self.zip_words(other, |a, b| a | b);
+    }
+
+    pub fn intersect_with(&mut self, other: &Self) {
+        self.zip_words(other, |a, b| a & b);
+    }
+
+    pub fn difference_with(&mut self, other: &Self) {
+        self.zip_words(other, |a, b| a & !b);
+    }
+
+    pub fn union(&self, other: &Self) -> Self {
+        let mut result = self.clone();
+        result.union_with(other);
+        result
+    }
+
+    pub fn intersection(&self, other: &Self) -> Self {
+        let mut result = self.clone();
+        result.intersect_with(other);
+        result
+    }
+}
+
+// A set of (cell, unit direction) pairs, e.g. for "been here heading this way" checks.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct DirBitGrid {
+    // one bit per `Adjacent` direction
+    cells: Vec<u8>,
+    rows: usize,
+    cols: usize,
+}
+
+impl DirBitGrid {
+    pub fn new(rows: usize, cols: usize) -> Self {
+        Self {
+            cells: vec![0; rows * cols],
+            rows,
+            cols,
+        }
+    }
+
+    fn bit(&self, index: GridIdx, dir: GridOffset) -> Option<(usize, u8)> {
+        assert!(
+            (-1..=1).contains(&dir.0) && (-1..=1).contains(&dir.1) && dir != GridOffset(0, 0),
+            "{dir:?} is not a unit step"
+        );
+
+        if !GridBounds::from_size(self.rows, self.cols).contains(index) {
+            return None;
+        }
+
+        // the 3x3 neighborhood numbered row-major, skipping the center
+        let slot = ((dir.0 + 1) * 3 + dir.1 + 1) as u32;
+        let slot = if slot > 4 { slot - 1 } else { slot };
+        Some((index.row() * self.cols + index.col(), 1 << slot))
+    }
+
+    // true if the pair was not already in the set; panics on out of bounds cells
+    pub fn insert(&mut self, index: GridIdx, dir: GridOffset) -> bool {
+        let Some((flat, mask)) = self.bit(index, dir) else {
+            panic!("{index:?} is outside the {}x{} grid", self.rows, self.cols);
+        };
+
+        let fresh = self.cells[flat] & mask == 0;
+        self.cells[flat] |= mask;
+        fresh
+    }
+
+    pub fn contains(&self, index: GridIdx, dir: GridOffset) -> bool {
+        self.bit(index, dir)
+            .is_some_and(|(flat, mask)| self.cells[flat] & mask != 0)
+    }
+
+    pub fn count(&self) -> usize {
+        self.cells.iter().map(|c| c.count_ones() as usize).sum()
+    }
+
+    pub fn clear(&mut self) {
+        self.cells.fill(0);
+    }
+
+    // cells visited in any direction
+    pub fn cells(&self) -> BitGrid {
+        let mut bits = BitGrid::new(self.rows, self.cols);
+        for (flat, dirs) in self.cells.iter().enumerate() {
+            if *dirs != 0 {
+                bits.insert(GridIdx::new(flat / self.cols, flat % self.cols));
+            }
+        }
+        bits
+    }
+
+    pub fn union_with(&mut self, other: &Self) {
+        assert_eq!(
+            self.cells.len(),
+            other.cells.len(),
+            "bit grids must be the same size"
+        );
+        for (a, b) in self.cells.iter_mut().zip(other.cells.iter()) {
+            *a |= b;
+        }
+    }
+
+    pub fn intersect_with(&mut self, other: &Self) {
+        assert_eq!(
+            self.cells.len(),
+            other.cells.len(),
+            "bit grids must be the same size"
+        );
+        for (a, b) in self.cells.iter_mut().zip(other.cells.iter()) {
+            *a &= b;
+        }
+    }
+}
+
+impl<T> Grid<T> {
+    pub fn bits(&self, pred: impl Fn(&T) -> bool) -> BitGrid {
+        let mut bits = BitGrid::new(self.rows, self.cols);
+        for idx in self.positions(pred) {
+            bits.insert(idx);
+        }
+        bits
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_bit_grid() {
+        let mut bits = BitGrid::new(9, 10);
+        assert!(bits.insert(GridIdx(0, 0)));
+        assert!(!bits.insert(GridIdx(0, 0)));
+        assert!(bits.insert(GridIdx(6, 4)));
+        assert!(bits.insert(GridIdx(8, 9)));
+        assert_eq!(bits.count(), 3);
+        assert!(bits.contains(GridIdx(6, 4)));
+        assert!(!bits.contains(GridIdx(4, 6)));
+
+        let cells: Vec<_> = bits.iter().collect();
+        assert_eq!(cells, [GridIdx(0, 0), GridIdx(6, 4), GridIdx(8, 9)]);
+
+        assert!(bits.remove(GridIdx(6, 4)));
+        assert!(!bits.remove(GridIdx(6, 4)));
+        assert!(!bits.remove(GridIdx(-1, 0)));
+        assert_eq!(bits.count(), 2);
+    }
+
+    #[test]
+    #[should_panic(expected = "outside the 9x10 grid")]
+    fn test_bit_grid_rejects_out_of_bounds() {
+        BitGrid::new(9, 10).insert(GridIdx(9, 0));
+    }
+
+    #[test]
+    fn test_set_operations() {
+        let grid = parse_char_grid("ab.\n.ab\nb.a");
+        let a = grid.bits(|c| *c == 'a');
+        let b = grid.bits(|c| *c == 'b');
+        let letters = grid.bits(|c| *c != '.');
+
+        assert_eq!(a.union(&b), letters);
+        assert!(a.intersection(&b).is_empty());
+
+        let mut only_b = letters.clone();
+        only_b.difference_with(&a);
+        assert_eq!(only_b, b);
+    }
+
+    #[test]
+    fn test_dir_bit_grid() {
+        let mut seen = DirBitGrid::new(3, 3);
+        let up = GridOffset(-1, 0);
+        let right = GridOffset(0, 1);
+        assert!(seen.insert(GridIdx(1, 1), up));
+        assert!(seen.insert(GridIdx(1, 1), right));
+        assert!(!seen.insert(GridIdx(1, 1), up));
+        assert!(seen.insert(GridIdx(2, 2), GridOffset(1, 1)));
+
+        assert!(seen.contains(GridIdx(1, 1), right));
+        assert!(!seen.contains(GridIdx(1, 1), GridOffset(0, -1)));
+        assert_eq!(seen.count(), 3);
+        assert_eq!(seen.cells().count(), 2);
+    }
+
+    #[test]
+    #[should_panic(expected = "outside the 3x3 grid")]
+    fn test_dir_bit_grid_rejects_out_of_bounds() {
+        DirBitGrid::new(3, 3).insert(GridIdx(3, 0), GridOffset(-1, 0));
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_dir_bit_grid_rejects_long_steps() {
+        DirBitGrid::new(3, 3).insert(GridIdx(0, 0), GridOffset(0, 2));
+    }
+}
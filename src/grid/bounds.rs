@@ -0,0 +1,140 @@
+use itertools::Itertools;
+
+use super::*;
+
+// An axis-aligned rectangle of indices between inclusive corners. Bounds where `max` is
+// below or left of `min` are empty.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub struct GridBounds {
+    pub min: GridIdx,
+    pub max: GridIdx,
+}
+
+impl GridBounds {
+    pub fn new(min: GridIdx, max: GridIdx) -> Self {
+        Self { min, max }
+    }
+
+    // `0..rows` x `0..cols`
+    pub fn from_size(rows: usize, cols: usize) -> Self {
+        Self::new(GridIdx(0, 0), GridIdx(rows as isize - 1, cols as isize - 1))
+    }
+
+    pub fn point(index: GridIdx) -> Self {
+        Self::new(index, index)
+    }
+
+    // smallest bounds containing every point, `None` without any
+    pub fn enclosing(points: impl IntoIterator<Item = GridIdx>) -> Option<Self> {
+        points
+            .into_iter()
+            .fold(None, |acc: Option<Self>, idx| match acc {
+                None => Some(Self::point(idx)),
+                Some(bounds) => Some(bounds.extend(idx)),
+            })
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.max.0 < self.min.0 || self.max.1 < self.min.1
+    }
+
+    pub fn size(&self) -> (usize, usize) {
+        let rows = (self.max.0 - self.min.0 + 1).max(0) as usize;
+        let cols = (self.max.1 - self.min.1 + 1).max(0) as usize;
+        (rows, cols)
+    }
+
+    pub fn contains(&self, index: GridIdx) -> bool {
+        (self.min.0..=self.max.0).contains(&index.0) && (self.min.1..=self.max.1).contains(&index.1)
+    }
+
+    // nearest index inside the bounds; panics when empty, like `Ord::clamp`
+    pub fn clamp(&self, index: GridIdx) -> GridIdx {
+        assert!(!self.is_empty(), "cannot clamp to empty bounds");
+        GridIdx(
+            index.0.clamp(self.min.0, self.max.0),
+            index.1.clamp(self.min.1, self.max.1),
+        )
+    }
+
+    // grown to include `index`; empty bounds become just `index`
+    pub fn extend(self, index: GridIdx) -> Self {
+        if self.is_empty() {
+            return Self::point(index);
+        }
+        Self::new(
+            GridIdx(self.min.0.min(index.0), self.min.1.min(index.1)),
+            GridIdx(self.max.0.max(index.0), self.max.1.max(index.1)),
+        )
+    }
+
+    // possibly empty
+    pub fn intersection(&self, other: &Self) -> Self {
+        Self::new(
+            GridIdx(self.min.0.max(other.min.0), self.min.1.max(other.min.1)),
+            GridIdx(self.max.0.min(other.max.0), self.max.1.min(other.max.1)),
+        )
+    }
+
+    // row-major order
+    pub fn iter(&self) -> impl Iterator<Item = GridIdx> + use<> {
+        (self.min.0..=self.max.0)
+            .cartesian_product(self.min.1..=self.max.1)
+            .map(|(r, c)| GridIdx(r, c))
+    }
+}
+
+impl<T> Grid<T> {
+    pub fn bounds(&self) -> GridBounds {
+        GridBounds::from_size(self.rows, self.cols)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_bounds() {
+        let bounds = GridBounds::from_size(2, 3);
+        assert_eq!(bounds.size(), (2, 3));
+        assert!(bounds.contains(GridIdx(1, 2)));
+        assert!(!bounds.contains(GridIdx(2, 0)));
+        assert!(!bounds.contains(GridIdx(0, -1)));
+        assert_eq!(bounds.clamp(GridIdx(-4, 7)), GridIdx(0, 2));
+        assert_eq!(bounds.iter().count(), 6);
+        assert_eq!(bounds.iter().nth(4), Some(GridIdx(1, 1)));
+
+        let empty = GridBounds::from_size(0, 3);
+        assert!(empty.is_empty());
+        assert_eq!(empty.size(), (0, 3));
+        assert_eq!(empty.iter().count(), 0);
+        assert!(!empty.contains(GridIdx(0, 0)));
+        assert_eq!(
+            GridBounds::from_size(0, 0).extend(GridIdx(2, 2)),
+            GridBounds::point(GridIdx(2, 2))
+        );
+    }
+
+    #[test]
+    fn test_intersection() {
+        let a = GridBounds::new(GridIdx(0, 0), GridIdx(4, 4));
+        let b = GridBounds::new(GridIdx(2, -3), GridIdx(6, 1));
+        assert_eq!(
+            a.intersection(&b),
+            GridBounds::new(GridIdx(2, 0), GridIdx(4, 1))
+        );
+
+        let c = GridBounds::point(GridIdx(9, 9));
+        assert!(a.intersection(&c).is_empty());
+    }
+
+    #[test]
+    fn test_enclosing() {
+        let points = [GridIdx(-2, 5), GridIdx(3, -1), GridIdx(0, 0)];
+        let bounds = GridBounds::enclosing(points).unwrap();
+        assert_eq!(bounds, GridBounds::new(GridIdx(-2, -1), GridIdx(3, 5)));
+        assert!(points.iter().all(|idx| bounds.contains(*idx)));
+        assert_eq!(GridBounds::enclosing([]), None);
+    }
+}
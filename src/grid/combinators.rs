@@ -0,0 +1,111 @@
+use std::collections::HashMap;
+use std::hash::Hash;
+
+use itertools::Itertools;
+
+use super::*;
+
+impl<T> Grid<T> {
+    pub fn enumerate(&self) -> impl Iterator<Item = (GridIdx, &T)> {
+        self.indices().zip(self.cells.iter())
+    }
+
+    pub fn map<U>(&self, f: impl FnMut(&T) -> U) -> Grid<U> {
+        Grid {
+            cells: self.cells.iter().map(f).collect(),
+            rows: self.rows,
+            cols: self.cols,
+            topology: self.topology,
+        }
+    }
+
+    // combines cells at the same position; panics if the grids differ in size
+    pub fn zip_with<U, V>(&self, other: &Grid<U>, mut f: impl FnMut(&T, &U) -> V) -> Grid<V> {
+        assert_eq!(
+            self.size(),
+            other.size(),
+            "zipped grids must be the same size"
+        );
+        Grid {
+            cells: self
+                .cells
+                .iter()
+                .zip(other.cells.iter())
+                .map(|(a, b)| f(a, b))
+                .collect(),
+            rows: self.rows,
+            cols: self.cols,
+            topology: self.topology,
+        }
+    }
+
+    pub fn count(&self, pred: impl Fn(&T) -> bool) -> usize {
+        self.cells.iter().filter(|cell| pred(cell)).count()
+    }
+
+    // every matching position in row-major order, unlike `position` which stops at the first
+    pub fn positions(&self, pred: impl Fn(&T) -> bool) -> impl Iterator<Item = GridIdx> {
+        self.enumerate()
+            .filter(move |(_, cell)| pred(cell))
+            .map(|(idx, _)| idx)
+    }
+}
+
+impl<T: Eq + Hash> Grid<T> {
+    pub fn histogram(&self) -> HashMap<&T, usize> {
+        self.cells.iter().counts()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const INPUT: &str = "ab.\n.ab\nb.a";
+
+    #[test]
+    fn test_map_and_zip() {
+        let grid = parse_char_grid(INPUT);
+        let letters = grid.map(|c| c.is_alphabetic());
+        assert_eq!(letters.size(), grid.size());
+        assert_eq!(letters.get(GridIdx(2, 0)), Some(&true));
+        assert_eq!(letters.get(GridIdx(2, 1)), Some(&false));
+
+        let upper = grid.map(|c| c.to_ascii_uppercase());
+        let zipped = grid.zip_with(&upper, |a, b| format!("{a}{b}"));
+        assert_eq!(zipped.get(GridIdx(0, 1)).unwrap(), "bB");
+    }
+
+    #[test]
+    fn test_enumerate_and_positions() {
+        let grid = parse_char_grid(INPUT);
+        let (idx, cell) = grid.enumerate().nth(5).unwrap();
+        assert_eq!((idx, *cell), (GridIdx(1, 2), 'b'));
+
+        let a: Vec<_> = grid.positions(|c| *c == 'a').collect();
+        assert_eq!(a, [GridIdx(0, 0), GridIdx(1, 1), GridIdx(2, 2)]);
+        assert_eq!(grid.count(|c| *c == '.'), 3);
+    }
+
+    #[test]
+    fn test_histogram() {
+        let grid = parse_char_grid(INPUT);
+        let histogram = grid.histogram();
+        assert_eq!(histogram.len(), 3);
+        assert!(histogram.values().all(|n| *n == 3));
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_zip_size_mismatch() {
+        let grid = parse_char_grid(INPUT);
+        grid.zip_with(
+            &grid
+                .rotate_cw()
+                .view(GridIdx(0, 0), 2, 3)
+                .unwrap()
+                .to_grid(),
+            |a, b| a == b,
+        );
+    }
+}
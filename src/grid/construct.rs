@@ -0,0 +1,126 @@
+use std::error::Error;
+use std::fmt;
+
+use super::*;
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct RaggedRows {
+    pub row: usize,
+    pub expected: usize,
+    pub found: usize,
+}
+
+impl fmt::Display for RaggedRows {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "row {} has {} cells, expected {}",
+            self.row, self.found, self.expected
+        )
+    }
+}
+
+impl Error for RaggedRows {}
+
+impl<T> Grid<T> {
+    pub fn from_fn(rows: usize, cols: usize, f: impl FnMut(GridIdx) -> T) -> Self {
+        Self {
+            cells: GridBounds::from_size(rows, cols).iter().map(f).collect(),
+            rows,
+            cols,
+            topology: Topology::Bounded,
+        }
+    }
+
+    // every row must be as long as the first
+    pub fn from_rows(rows: Vec<Vec<T>>) -> Result<Self, RaggedRows> {
+        let cols = rows.first().map_or(0, Vec::len);
+        if let Some((row, found)) = rows
+            .iter()
+            .map(Vec::len)
+            .enumerate()
+            .find(|(_, len)| *len != cols)
+        {
+            return Err(RaggedRows {
+                row,
+                expected: cols,
+                found,
+            });
+        }
+
+        Ok(Self {
+            rows: rows.len(),
+            cols,
+            cells: rows.into_iter().flatten().collect(),
+            topology: Topology::Bounded,
+        })
+    }
+}
+
+impl<T: Clone> Grid<T> {
+    pub fn new(rows: usize, cols: usize, fill: T) -> Self {
+        Self {
+            cells: vec![fill; rows * cols],
+            rows,
+            cols,
+            topology: Topology::Bounded,
+        }
+    }
+
+    // Just large enough to hold every point, from row and column 0, with `default` in the
+    // cells not given. Panics on negative indices.
+    pub fn from_sparse(points: impl IntoIterator<Item = (GridIdx, T)>, default: T) -> Self {
+        let points: Vec<_> = points.into_iter().collect();
+        let (rows, cols) = match GridBounds::enclosing(points.iter().map(|(idx, _)| *idx)) {
+            Some(bounds) => (bounds.max.row() + 1, bounds.max.col() + 1),
+            None => (0, 0),
+        };
+        let mut grid = Self::new(rows, cols, default);
+        for (idx, cell) in points {
+            grid.cells[idx.row() * cols + idx.col()] = cell;
+        }
+        grid
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_constructors() {
+        let grid = parse_char_grid("ab\ncd\nef");
+        assert_eq!(Grid::new(3, 2, '.').to_string(), "..\n..\n..");
+        assert_eq!(
+            Grid::from_fn(3, 2, |idx| grid.get(idx).copied().unwrap()),
+            grid
+        );
+        let rows = vec![vec!['a', 'b'], vec!['c', 'd'], vec!['e', 'f']];
+        assert_eq!(Grid::from_rows(rows), Ok(grid));
+
+        let empty = Grid::<char>::from_rows(Vec::new()).unwrap();
+        assert_eq!(empty.size(), (0, 0));
+        assert_eq!(Grid::from_fn(0, 4, |_| 0).cells.len(), 0);
+    }
+
+    #[test]
+    fn test_ragged_rows() {
+        let err = Grid::from_rows(vec![vec![1, 2], vec![3, 4], vec![5]]).unwrap_err();
+        assert_eq!(
+            err,
+            RaggedRows {
+                row: 2,
+                expected: 2,
+                found: 1
+            }
+        );
+        assert_eq!(err.to_string(), "row 2 has 1 cells, expected 2");
+    }
+
+    #[test]
+    fn test_from_sparse() {
+        let grid = Grid::from_sparse([(GridIdx(1, 3), '#'), (GridIdx(0, 1), '@')], '.');
+        assert_eq!(grid.to_string(), ".@..\n...#");
+        assert_eq!(Grid::from_sparse([], 0).size(), (0, 0));
+    }
+}
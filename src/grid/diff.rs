@@ -0,0 +1,189 @@
+use super::*;
+
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct CellChange<'a, T> {
+    pub at: GridIdx,
+    // `None` where the position is outside that grid
+    pub old: Option<&'a T>,
+    pub new: Option<&'a T>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct GridDiff<'a, T> {
+    pub old: &'a Grid<T>,
+    pub new: &'a Grid<T>,
+    // row-major over the area covered by either grid
+    pub changes: Vec<CellChange<'a, T>>,
+}
+
+// the stored cell, without wrapping on a wrapping or infinite grid
+fn stored<T>(grid: &Grid<T>, at: GridIdx) -> Option<&T> {
+    grid.bounds()
+        .contains(at)
+        .then(|| &grid.cells[at.row() * grid.cols + at.col()])
+}
+
+impl<T: PartialEq> Grid<T> {
+    pub fn diff<'a>(&'a self, other: &'a Grid<T>) -> GridDiff<'a, T> {
+        let (rows, cols) = self.size();
+        let (other_rows, other_cols) = other.size();
+        let area = GridBounds::from_size(rows.max(other_rows), cols.max(other_cols));
+        let changes = area
+            .iter()
+            .map(|at| CellChange {
+                at,
+                old: stored(self, at),
+                new: stored(other, at),
+            })
+            .filter(|change| change.old != change.new)
+            .collect();
+
+        GridDiff {
+            old: self,
+            new: other,
+            changes,
+        }
+    }
+}
+
+impl<T> GridDiff<'_, T> {
+    pub fn is_empty(&self) -> bool {
+        self.changes.is_empty()
+    }
+
+    pub fn size_mismatch(&self) -> bool {
+        self.old.size() != self.new.size()
+    }
+}
+
+impl<T: CellSymbol> GridDiff<'_, T> {
+    // The old grid, the new grid and a mask with `*` at every change, side by side. Rows
+    // with a change are flagged with `<`.
+    pub fn side_by_side(&self) -> String {
+        let (old_rows, old_cols) = self.old.size();
+        let (new_rows, new_cols) = self.new.size();
+        let headers = [
+            format!("old {old_rows}x{old_cols}"),
+            format!("new {new_rows}x{new_cols}"),
+            "diff".to_string(),
+        ];
+        let cols = old_cols.max(new_cols);
+        let width = headers.iter().map(String::len).max().unwrap().max(cols);
+
+        let mut changed = BitGrid::new(old_rows.max(new_rows), cols);
+        for change in &self.changes {
+            changed.insert(change.at);
+        }
+
+        let symbols = |grid: &Grid<T>, row: usize| -> String {
+            (0..cols)
+                .map(|col| stored(grid, GridIdx::new(row, col)).map_or(' ', CellSymbol::symbol))
+                .collect()
+        };
+
+        let mut lines = vec![format!(
+            "{:width$}   {:width$}   {}",
+            headers[0], headers[1], headers[2]
+        )];
+        for row in 0..old_rows.max(new_rows) {
+            let mask: String = (0..cols)
+                .map(|col| {
+                    if changed.contains(GridIdx::new(row, col)) {
+                        '*'
+                    } else {
+                        '.'
+                    }
+                })
+                .collect();
+            let flag = if mask.contains('*') { " <" } else { "" };
+            lines.push(format!(
+                "{:width$} | {:width$} | {mask}{flag}",
+                symbols(self.old, row),
+                symbols(self.new, row),
+            ));
+        }
+        lines.join("\n")
+    }
+}
+
+// `assert_eq!` for grids, printing a side-by-side diff on failure
+#[track_caller]
+pub fn assert_grid_eq<T: CellSymbol + PartialEq>(actual: &Grid<T>, expected: &Grid<T>) {
+    let diff = actual.diff(expected);
+    if !diff.is_empty() {
+        panic!(
+            "grids differ in {} cells{}\n{}",
+            diff.changes.len(),
+            if diff.size_mismatch() {
+                " and in size"
+            } else {
+                ""
+            },
+            diff.side_by_side()
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_diff() {
+        let a = parse_char_grid("abc\ndef");
+        let b = parse_char_grid("abc\nxeF");
+        let diff = a.diff(&b);
+        assert!(!diff.size_mismatch());
+        let changes: Vec<_> = diff
+            .changes
+            .iter()
+            .map(|c| (c.at, *c.old.unwrap(), *c.new.unwrap()))
+            .collect();
+        assert_eq!(
+            changes,
+            [(GridIdx(1, 0), 'd', 'x'), (GridIdx(1, 2), 'f', 'F')]
+        );
+        assert!(a.diff(&a).is_empty());
+    }
+
+    #[test]
+    fn test_size_mismatch() {
+        let a = parse_char_grid("ab\ncd");
+        let b = parse_char_grid("abz");
+        let diff = a.diff(&b);
+        assert!(diff.size_mismatch());
+        assert_eq!(diff.changes.len(), 3);
+        assert_eq!(diff.changes[0].old, None);
+        assert_eq!(diff.changes[0].new, Some(&'z'));
+
+        // cells outside a wrapping grid are missing too, not wrapped
+        let wrapped_a = a.clone().with_topology(Topology::Wrapping);
+        let wrapped_b = b.clone().with_topology(Topology::Wrapping);
+        let wrapped = wrapped_a.diff(&wrapped_b);
+        assert_eq!(wrapped.changes, diff.changes);
+        assert_eq!(wrapped.side_by_side(), diff.side_by_side());
+
+        assert_eq!(
+            diff.side_by_side(),
+            [
+                "old 2x2   new 1x3   diff",
+                "ab      | abz     | ..* <",
+                "cd      |         | **. <",
+            ]
+            .join("\n")
+        );
+    }
+
+    #[test]
+    fn test_assert_grid_eq() {
+        let grid = parse_char_grid("ab\ncd");
+        assert_grid_eq(&grid.rotate_cw().rotate_ccw(), &grid);
+
+        let failure = std::panic::catch_unwind(|| {
+            assert_grid_eq(&grid.transpose(), &grid);
+        });
+        let message = *failure.unwrap_err().downcast::<String>().unwrap();
+        assert!(message.starts_with("grids differ in 2 cells\n"));
+        assert!(message.ends_with("bd      | cd      | *. <"));
+    }
+}
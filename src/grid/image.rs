@@ -0,0 +1,376 @@
+use std::fmt::Write as _;
+use std::io;
+use std::path::Path;
+
+use itertools::Itertools;
+
+use super::*;
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub struct Rgb(pub u8, pub u8, pub u8);
+
+impl Rgb {
+    pub const BLACK: Rgb = Rgb(0, 0, 0);
+    pub const WHITE: Rgb = Rgb(255, 255, 255);
+    pub const GRAY: Rgb = Rgb(128, 128, 128);
+    pub const RED: Rgb = Rgb(220, 50, 47);
+    pub const GREEN: Rgb = Rgb(133, 153, 0);
+    pub const BLUE: Rgb = Rgb(38, 139, 210);
+    pub const YELLOW: Rgb = Rgb(181, 137, 0);
+
+    pub fn hex(self) -> String {
+        format!("#{:02x}{:02x}{:02x}", self.0, self.1, self.2)
+    }
+
+    // a spread of distinct hues for labelling groups, e.g. antenna frequencies
+    pub fn categorical(n: usize) -> Rgb {
+        const HUES: [Rgb; 8] = [
+            Rgb(38, 139, 210),
+            Rgb(220, 50, 47),
+            Rgb(133, 153, 0),
+            Rgb(211, 54, 130),
+            Rgb(42, 161, 152),
+            Rgb(203, 75, 22),
+            Rgb(108, 113, 196),
+            Rgb(181, 137, 0),
+        ];
+        HUES[n % HUES.len()]
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Shape {
+    Cells(Vec<GridIdx>),
+    // consecutive points joined by straight lines
+    Path(Vec<GridIdx>),
+}
+
+// A grid colored cell by cell with shapes drawn over it, exportable as PPM, PNG or SVG.
+// Raster formats draw every cell as a `scale` x `scale` block and lines as the cells they
+// cross; SVG draws lines through cell centers.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Picture {
+    colors: Grid<Rgb>,
+    overlays: Vec<(Shape, Rgb)>,
+    scale: usize,
+}
+
+impl<T> Grid<T> {
+    pub fn picture(&self, palette: impl Fn(&T) -> Rgb) -> Picture {
+        Picture {
+            colors: self.map(palette),
+            overlays: Vec::new(),
+            scale: 1,
+        }
+    }
+}
+
+impl Picture {
+    pub fn with_scale(mut self, scale: usize) -> Self {
+        assert!(scale > 0, "scale must be positive");
+        self.scale = scale;
+        self
+    }
+
+    pub fn cells(mut self, cells: impl IntoIterator<Item = GridIdx>, color: Rgb) -> Self {
+        self.overlays
+            .push((Shape::Cells(cells.into_iter().collect()), color));
+        self
+    }
+
+    pub fn path(mut self, points: impl IntoIterator<Item = GridIdx>, color: Rgb) -> Self {
+        self.overlays
+            .push((Shape::Path(points.into_iter().collect()), color));
+        self
+    }
+
+    pub fn line(self, a: GridIdx, b: GridIdx, color: Rgb) -> Self {
+        self.path([a, b], color)
+    }
+
+    // pixel size
+    pub fn size(&self) -> (usize, usize) {
+        (self.colors.rows * self.scale, self.colors.cols * self.scale)
+    }
+
+    pub fn is_empty(&self) -> bool {
+        let (height, width) = self.size();
+        height == 0 || width == 0
+    }
+
+    // cell colors with the overlays painted on, in order
+    pub fn raster(&self) -> Grid<Rgb> {
+        let mut colors = self.colors.clone();
+        for (shape, color) in &self.overlays {
+            match shape {
+                Shape::Cells(cells) => {
+                    for idx in cells {
+                        colors.put(*idx, *color);
+                    }
+                }
+                Shape::Path(points) => {
+                    for idx in points.iter().take(1) {
+                        colors.put(*idx, *color);
+                    }
+                    for (a, b) in points.iter().zip(points.iter().skip(1)) {
+                        for idx in line::bresenham(*a, *b) {
+                            colors.put(idx, *color);
+                        }
+                    }
+                }
+            }
+        }
+        colors
+    }
+
+    // row-major RGB triples, scaled up
+    fn pixels(&self) -> Vec<u8> {
+        let raster = self.raster();
+        let (height, width) = self.size();
+        let mut pixels = Vec::with_capacity(height * width * 3);
+        for y in 0..height {
+            for x in 0..width {
+                let Rgb(r, g, b) = raster.cells[(y / self.scale) * raster.cols + x / self.scale];
+                pixels.extend([r, g, b]);
+            }
+        }
+        pixels
+    }
+
+    // binary portable pixmap (P6)
+    pub fn to_ppm(&self) -> Vec<u8> {
+        let (height, width) = self.size();
+        let mut ppm = format!("P6\n{width} {height}\n255\n").into_bytes();
+        ppm.extend(self.pixels());
+        ppm
+    }
+
+    // 8-bit truecolor PNG with uncompressed (stored) deflate blocks
+    // PNG has no empty images: a grid without rows or columns still gets a well-formed file,
+    // but with a zero dimension that readers reject. `save` refuses to write one.
+    pub fn to_png(&self) -> Vec<u8> {
+        let (height, width) = self.size();
+        let pixels = self.pixels();
+
+        // every scanline starts with filter type 0 (none)
+        let line = width * 3;
+        let mut raw = Vec::with_capacity(pixels.len() + height);
+        for y in 0..height {
+            raw.push(0);
+            raw.extend(&pixels[y * line..(y + 1) * line]);
+        }
+
+        let mut ihdr = Vec::with_capacity(13);
+        ihdr.extend((width as u32).to_be_bytes());
+        ihdr.extend((height as u32).to_be_bytes());
+        ihdr.extend([8, 2, 0, 0, 0]);
+
+        let mut png = b"\x89PNG\r\n\x1a\n".to_vec();
+        png_chunk(&mut png, b"IHDR", &ihdr);
+        png_chunk(&mut png, b"IDAT", &zlib_stored(&raw));
+        png_chunk(&mut png, b"IEND", &[]);
+        png
+    }
+
+    pub fn to_svg(&self) -> String {
+        let (rows, cols) = self.colors.size();
+        let (height, width) = self.size();
+        let mut svg = String::new();
+        writeln!(
+            svg,
+            r#"<svg xmlns="http://www.w3.org/2000/svg" width="{width}" height="{height}" viewBox="0 0 {cols} {rows}" shape-rendering="crispEdges">"#
+        )
+        .unwrap();
+
+        let rect = |svg: &mut String, idx: GridIdx, color: Rgb| {
+            let (GridIdx(y, x), fill) = (idx, color.hex());
+            writeln!(
+                svg,
+                r#"<rect x="{x}" y="{y}" width="1" height="1" fill="{fill}"/>"#
+            )
+            .unwrap();
+        };
+
+        for (idx, color) in self.colors.enumerate() {
+            rect(&mut svg, idx, *color);
+        }
+
+        for (shape, color) in &self.overlays {
+            match shape {
+                Shape::Cells(cells) => {
+                    for idx in cells.iter().filter(|idx| self.colors.get(**idx).is_some()) {
+                        rect(&mut svg, *idx, *color);
+                    }
+                }
+                Shape::Path(points) => {
+                    let points = points
+                        .iter()
+                        .map(|GridIdx(y, x)| format!("{},{}", *x as f64 + 0.5, *y as f64 + 0.5))
+                        .join(" ");
+                    writeln!(
+                        svg,
+                        r#"<polyline points="{points}" fill="none" stroke="{}" stroke-width="0.25" stroke-linecap="round" stroke-linejoin="round" shape-rendering="auto"/>"#,
+                        color.hex()
+                    )
+                    .unwrap();
+                }
+            }
+        }
+
+        svg.push_str("</svg>\n");
+        svg
+    }
+
+    // format from the extension: `ppm`, `png` or `svg`
+    pub fn save(&self, path: impl AsRef<Path>) -> io::Result<()> {
+        let path = path.as_ref();
+        let bytes = match path.extension().and_then(|ext| ext.to_str()) {
+            Some("ppm") => self.to_ppm(),
+            Some("png") if self.is_empty() => {
+                let msg = format!("cannot save an empty image as png: {}", path.display());
+                return Err(io::Error::new(io::ErrorKind::InvalidInput, msg));
+            }
+            Some("png") => self.to_png(),
+            Some("svg") => self.to_svg().into_bytes(),
+            _ => {
+                let msg = format!("unsupported image format: {}", path.display());
+                return Err(io::Error::new(io::ErrorKind::InvalidInput, msg));
+            }
+        };
+        std::fs::write(path, bytes)
+    }
+}
+
+fn png_chunk(png: &mut Vec<u8>, kind: &[u8; 4], data: &[u8]) {
+    png.extend((data.len() as u32).to_be_bytes());
+    let start = png.len();
+    png.extend(kind);
+    png.extend(data);
+    let crc = crc32(&png[start..]);
+    png.extend(crc.to_be_bytes());
+}
+
+fn zlib_stored(data: &[u8]) -> Vec<u8> {
+    const MAX_BLOCK: usize = u16::MAX as usize;
+
+    let mut out = vec![0x78, 0x01];
+    let mut blocks = data.chunks(MAX_BLOCK).peekable();
+    if blocks.peek().is_none() {
+        out.extend([1, 0, 0, 0xff, 0xff]);
+    }
+    while let Some(block) = blocks.next() {
+        let last = blocks.peek().is_none();
+        let len = block.len() as u16;
+        out.push(last as u8);
+        out.extend(len.to_le_bytes());
+        out.extend((!len).to_le_bytes());
+        out.extend(block);
+    }
+    out.extend(adler32(data).to_be_bytes());
+    out
+}
+
+fn crc32(data: &[u8]) -> u32 {
+    let mut crc = !0u32;
+    for byte in data {
+        crc ^= *byte as u32;
+        for _ in 0..8 {
+            let mask = (crc & 1).wrapping_neg();
+            crc = (crc >> 1) ^ (0xedb8_8320 & mask);
+        }
+    }
+    !crc
+}
+
+fn adler32(data: &[u8]) -> u32 {
+    let (mut a, mut b) = (1u32, 0u32);
+    for byte in data {
+        a = (a + *byte as u32) % 65521;
+        b = (b + a) % 65521;
+    }
+    (b << 16) | a
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn picture() -> Picture {
+        let grid = parse_char_grid("#..\n...");
+        grid.picture(|c| if *c == '#' { Rgb::BLACK } else { Rgb::WHITE })
+            .line(GridIdx(1, 0), GridIdx(1, 2), Rgb::RED)
+            .with_scale(2)
+    }
+
+    #[test]
+    fn test_checksums() {
+        assert_eq!(crc32(b"IEND"), 0xae42_6082);
+        assert_eq!(crc32(b"123456789"), 0xcbf4_3926);
+        assert_eq!(adler32(b"Wikipedia"), 0x11e6_0398);
+    }
+
+    #[test]
+    fn test_raster() {
+        let raster = picture().raster();
+        assert_eq!(raster.get(GridIdx(0, 0)), Some(&Rgb::BLACK));
+        assert_eq!(raster.get(GridIdx(0, 1)), Some(&Rgb::WHITE));
+        assert_eq!(
+            raster.row(1).values().copied().collect::<Vec<_>>(),
+            [Rgb::RED; 3]
+        );
+    }
+
+    #[test]
+    fn test_ppm() {
+        let ppm = picture().to_ppm();
+        let header = b"P6\n6 4\n255\n";
+        assert!(ppm.starts_with(header));
+        assert_eq!(ppm.len(), header.len() + 6 * 4 * 3);
+        // the black cell covers the top left 2x2 pixels
+        assert_eq!(ppm[header.len()..header.len() + 6], [0; 6]);
+    }
+
+    #[test]
+    fn test_png() {
+        let png = picture().to_png();
+        assert!(png.starts_with(b"\x89PNG\r\n\x1a\n"));
+        assert_eq!(png[12..16], *b"IHDR");
+        assert_eq!(png[16..24], [0, 0, 0, 6, 0, 0, 0, 4]);
+        assert!(png.ends_with(&[0, 0, 0, 0, b'I', b'E', b'N', b'D', 0xae, 0x42, 0x60, 0x82]));
+
+        // 4 scanlines of a filter byte and 6 pixels, in one stored block
+        let idat = &png[33..];
+        assert_eq!(
+            u32::from_be_bytes(idat[..4].try_into().unwrap()),
+            2 + 5 + 4 * 19 + 4
+        );
+        assert_eq!(idat[4..8], *b"IDAT");
+    }
+
+    #[test]
+    fn test_empty_png() {
+        for (rows, cols) in [(0, 0), (3, 0), (0, 2)] {
+            let grid = Grid::new(rows, cols, 0);
+            let picture = grid.picture(|_| Rgb::BLACK);
+            assert!(picture.is_empty());
+            let png = picture.to_png();
+            assert_eq!(png[16..24], [0, 0, 0, cols as u8, 0, 0, 0, rows as u8]);
+
+            let path = std::env::temp_dir().join("aoc2024_empty.png");
+            let err = picture.save(&path).unwrap_err();
+            assert_eq!(err.kind(), io::ErrorKind::InvalidInput);
+        }
+    }
+
+    #[test]
+    fn test_svg() {
+        let svg = picture().to_svg();
+        assert!(svg.starts_with("<svg"));
+        assert!(svg.contains(r#"width="6" height="4" viewBox="0 0 3 2""#));
+        assert_eq!(svg.matches("<rect").count(), 6);
+        assert!(
+            svg.contains(r##"<polyline points="0.5,1.5 2.5,1.5" fill="none" stroke="#dc322f""##)
+        );
+        assert!(svg.trim_end().ends_with("</svg>"));
+    }
+}
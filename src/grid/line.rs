@@ -0,0 +1,134 @@
+use super::*;
+
+// every lattice point on the segment from `a` to `b`, both included
+pub fn lattice_points(a: GridIdx, b: GridIdx) -> impl Iterator<Item = GridIdx> {
+    let offset = b - a;
+    let step = offset.gcd_normalize();
+    let n = gcd(offset.0, offset.1);
+    (0..=n).map(move |i| a + step * i)
+}
+
+// 8-connected rasterization of the segment from `a` to `b`, both included
+pub fn bresenham(a: GridIdx, b: GridIdx) -> impl Iterator<Item = GridIdx> {
+    let offset = b - a;
+    let dc = offset.col().abs();
+    let dr = -offset.row().abs();
+    let step = offset.signum();
+    let mut err = dc + dr;
+    let mut next = Some(a);
+    std::iter::from_fn(move || {
+        let current = next?;
+        next = (current != b).then(|| {
+            let mut moved = current;
+            let e2 = 2 * err;
+            if e2 >= dr {
+                err += dr;
+                moved.1 += step.1;
+            }
+            if e2 <= dc {
+                err += dc;
+                moved.0 += step.0;
+            }
+            moved
+        });
+        Some(current)
+    })
+}
+
+// `start`, `start + step`, ... while `in_bounds` holds, for a non-zero `step`
+pub fn ray_within(
+    start: GridIdx,
+    step: GridOffset,
+    in_bounds: impl Fn(GridIdx) -> bool,
+) -> impl Iterator<Item = GridIdx> {
+    assert!(!step.is_zero(), "cannot step a ray by a zero offset");
+    std::iter::successors(Some(start), move |idx| Some(*idx + step))
+        .take_while(move |idx| in_bounds(*idx))
+}
+
+// Every lattice point on the infinite line through `a` and `b` that lies within `bounds`,
+// in order from where the line enters them to where it leaves. `a` and `b` themselves may
+// be outside.
+pub fn line_within(a: GridIdx, b: GridIdx, bounds: GridBounds) -> impl Iterator<Item = GridIdx> {
+    assert!(a != b, "a line needs two distinct points");
+    let step = (b - a).gcd_normalize();
+    let rows = steps_within(a.0, step.0, bounds.min.0, bounds.max.0);
+    let cols = steps_within(a.1, step.1, bounds.min.1, bounds.max.1);
+    (rows.0.max(cols.0)..=rows.1.min(cols.1)).map(move |i| a + step * i)
+}
+
+// the range of `i` that keeps `pos + step * i` within `min..=max`
+fn steps_within(pos: isize, step: isize, min: isize, max: isize) -> (isize, isize) {
+    let (lo, hi) = match step.signum() {
+        0 if (min..=max).contains(&pos) => return (isize::MIN, isize::MAX),
+        0 => return (1, 0),
+        1 => (min - pos, max - pos),
+        _ => (pos - max, pos - min),
+    };
+    let step = step.abs();
+    (-(-lo).div_euclid(step), hi.div_euclid(step))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_lattice_points() {
+        let points: Vec<_> = lattice_points(GridIdx(0, 0), GridIdx(4, 6)).collect();
+        assert_eq!(points, [GridIdx(0, 0), GridIdx(2, 3), GridIdx(4, 6)]);
+        assert_eq!(lattice_points(GridIdx(1, 1), GridIdx(1, 1)).count(), 1);
+    }
+
+    #[test]
+    fn test_bresenham() {
+        let points: Vec<_> = bresenham(GridIdx(0, 0), GridIdx(2, 5)).collect();
+        assert_eq!(
+            points,
+            [
+                GridIdx(0, 0),
+                GridIdx(0, 1),
+                GridIdx(1, 2),
+                GridIdx(1, 3),
+                GridIdx(2, 4),
+                GridIdx(2, 5),
+            ]
+        );
+
+        let back: Vec<_> = bresenham(GridIdx(3, 0), GridIdx(0, 0)).collect();
+        assert_eq!(back.len(), 4);
+        assert_eq!(back.last(), Some(&GridIdx(0, 0)));
+
+        for end in [GridIdx(-3, 7), GridIdx(5, -2), GridIdx(4, 4)] {
+            let points: Vec<_> = bresenham(GridIdx(0, 0), end).collect();
+            assert_eq!(points.last(), Some(&end));
+            assert_eq!(points.len(), end.chebyshev(GridIdx(0, 0)) + 1);
+        }
+    }
+
+    #[test]
+    fn test_line_within() {
+        let grid = parse_char_grid("....\n....\n....\n....\n....");
+        let in_bounds = |idx| grid.get(idx).is_some();
+        let points: Vec<_> = line_within(GridIdx(2, 1), GridIdx(4, 3), grid.bounds()).collect();
+        assert_eq!(
+            points,
+            [GridIdx(1, 0), GridIdx(2, 1), GridIdx(3, 2), GridIdx(4, 3)]
+        );
+
+        // neither point has to be inside
+        let bounds = GridBounds::from_size(5, 5);
+        let points: Vec<_> = line_within(GridIdx(-1, -1), GridIdx(0, 0), bounds).collect();
+        assert_eq!(points, (0..5).map(|i| GridIdx(i, i)).collect::<Vec<_>>());
+        let points: Vec<_> = line_within(GridIdx(9, 4), GridIdx(6, 3), bounds).collect();
+        assert_eq!(points, [GridIdx(3, 2), GridIdx(0, 1)]);
+        assert_eq!(
+            line_within(GridIdx(-1, 0), GridIdx(0, 1), bounds).count(),
+            4
+        );
+        assert_eq!(line_within(GridIdx(7, 0), GridIdx(7, 1), bounds).count(), 0);
+
+        let points: Vec<_> = ray_within(GridIdx(0, 0), GridOffset(2, 1), in_bounds).collect();
+        assert_eq!(points, [GridIdx(0, 0), GridIdx(2, 1), GridIdx(4, 2)]);
+    }
+}
@@ -0,0 +1,711 @@
+use core::ops::{Add, AddAssign, Div, Mul, Neg, Sub, SubAssign};
+
+use strum::IntoEnumIterator;
+
+mod automaton;
+mod bits;
+mod bounds;
+mod combinators;
+mod construct;
+mod diff;
+mod image;
+pub mod line;
+mod nd;
+mod parallel;
+mod pattern;
+mod ray;
+mod region;
+mod replay;
+pub mod search;
+mod sparse;
+mod symbol;
+mod topology;
+mod transform;
+mod view;
+
+pub use automaton::*;
+pub use bits::*;
+pub use bounds::*;
+pub use construct::*;
+pub use diff::*;
+pub use image::*;
+pub use nd::*;
+pub use pattern::*;
+pub use ray::*;
+pub use region::*;
+pub use replay::*;
+pub use sparse::*;
+pub use symbol::*;
+pub use topology::*;
+pub use transform::*;
+pub use view::*;
+
+pub use grid_derive::GridCell;
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Horz {
+    Left,
+    Right,
+}
+
+impl From<Horz> for GridOffset {
+    fn from(value: Horz) -> Self {
+        match value {
+            Horz::Left => GridOffset(0, -1),
+            Horz::Right => GridOffset(0, 1),
+        }
+    }
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Vert {
+    Up,
+    Down,
+}
+
+impl From<Vert> for GridOffset {
+    fn from(value: Vert) -> Self {
+        match value {
+            Vert::Up => GridOffset(-1, 0),
+            Vert::Down => GridOffset(1, 0),
+        }
+    }
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, strum::EnumIter)]
+pub enum Adjacent {
+    UL,
+    U,
+    UR,
+    L,
+    R,
+    DL,
+    D,
+    DR,
+}
+
+impl From<Adjacent> for GridOffset {
+    fn from(value: Adjacent) -> Self {
+        match value {
+            Adjacent::UL => Self::from(Vert::Up) + Self::from(Horz::Left),
+            Adjacent::U => Self::from(Vert::Up),
+            Adjacent::UR => Self::from(Vert::Up) + Self::from(Horz::Right),
+            Adjacent::L => Self::from(Horz::Left),
+            Adjacent::R => Self::from(Horz::Right),
+            Adjacent::DL => Self::from(Vert::Down) + Self::from(Horz::Left),
+            Adjacent::D => Self::from(Vert::Down),
+            Adjacent::DR => Self::from(Vert::Down) + Self::from(Horz::Right),
+        }
+    }
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum Connectivity {
+    Four,
+    Eight,
+}
+
+impl Connectivity {
+    pub fn offsets(self) -> impl Iterator<Item = GridOffset> {
+        Adjacent::iter()
+            .filter(move |adj| match self {
+                Self::Four => matches!(adj, Adjacent::U | Adjacent::L | Adjacent::R | Adjacent::D),
+                Self::Eight => true,
+            })
+            .map(GridOffset::from)
+    }
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct GridOffset(pub isize, pub isize);
+
+impl GridOffset {
+    pub fn row(self) -> isize {
+        self.0
+    }
+
+    pub fn col(self) -> isize {
+        self.1
+    }
+
+    pub fn is_zero(self) -> bool {
+        self == Self(0, 0)
+    }
+
+    pub fn signum(self) -> Self {
+        Self(self.0.signum(), self.1.signum())
+    }
+
+    pub fn manhattan(self) -> usize {
+        self.0.unsigned_abs() + self.1.unsigned_abs()
+    }
+
+    pub fn chebyshev(self) -> usize {
+        self.0.unsigned_abs().max(self.1.unsigned_abs())
+    }
+
+    // divided by the gcd of its components, the smallest step that still visits every
+    // lattice point along it
+    pub fn gcd_normalize(self) -> Self {
+        match gcd(self.0, self.1) {
+            0 => self,
+            n => self / n,
+        }
+    }
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct GridIdx(pub isize, pub isize);
+
+impl GridIdx {
+    pub fn new(row: usize, col: usize) -> Self {
+        Self(row as isize, col as isize)
+    }
+
+    // panics on a negative row, see `try_row` where that can happen
+    pub fn row(self) -> usize {
+        self.try_row().expect("negative row index")
+    }
+
+    pub fn col(self) -> usize {
+        self.try_col().expect("negative column index")
+    }
+
+    pub fn try_row(self) -> Option<usize> {
+        usize::try_from(self.0).ok()
+    }
+
+    pub fn try_col(self) -> Option<usize> {
+        usize::try_from(self.1).ok()
+    }
+
+    pub fn manhattan(self, other: Self) -> usize {
+        (self - other).manhattan()
+    }
+
+    pub fn chebyshev(self, other: Self) -> usize {
+        (self - other).chebyshev()
+    }
+}
+
+impl From<(isize, isize)> for GridOffset {
+    fn from((row, col): (isize, isize)) -> Self {
+        Self(row, col)
+    }
+}
+
+impl From<GridOffset> for (isize, isize) {
+    fn from(value: GridOffset) -> Self {
+        (value.0, value.1)
+    }
+}
+
+impl From<(isize, isize)> for GridIdx {
+    fn from((row, col): (isize, isize)) -> Self {
+        Self(row, col)
+    }
+}
+
+impl From<GridIdx> for (isize, isize) {
+    fn from(value: GridIdx) -> Self {
+        (value.0, value.1)
+    }
+}
+
+impl Add for GridOffset {
+    type Output = Self;
+
+    fn add(self, rhs: Self) -> Self::Output {
+        Self(self.0 + rhs.0, self.1 + rhs.1)
+    }
+}
+
+impl Sub for GridOffset {
+    type Output = Self;
+
+    fn sub(self, rhs: Self) -> Self::Output {
+        Self(self.0 - rhs.0, self.1 - rhs.1)
+    }
+}
+
+impl Neg for GridOffset {
+    type Output = Self;
+
+    fn neg(self) -> Self::Output {
+        Self(-self.0, -self.1)
+    }
+}
+
+impl AddAssign for GridOffset {
+    fn add_assign(&mut self, rhs: Self) {
+        *self = *self + rhs;
+    }
+}
+
+impl SubAssign for GridOffset {
+    fn sub_assign(&mut self, rhs: Self) {
+        *self = *self - rhs;
+    }
+}
+
+impl Add<GridOffset> for GridIdx {
+    type Output = Self;
+
+    fn add(self, rhs: GridOffset) -> Self::Output {
+        Self(self.0 + rhs.0, self.1 + rhs.1)
+    }
+}
+
+impl Sub for GridIdx {
+    type Output = GridOffset;
+
+    fn sub(self, rhs: Self) -> Self::Output {
+        GridOffset(self.0 - rhs.0, self.1 - rhs.1)
+    }
+}
+
+impl Sub<GridOffset> for GridIdx {
+    type Output = Self;
+
+    fn sub(self, rhs: GridOffset) -> Self::Output {
+        Self(self.0 - rhs.0, self.1 - rhs.1)
+    }
+}
+
+impl AddAssign<GridOffset> for GridIdx {
+    fn add_assign(&mut self, rhs: GridOffset) {
+        *self = *self + rhs;
+    }
+}
+
+impl SubAssign<GridOffset> for GridIdx {
+    fn sub_assign(&mut self, rhs: GridOffset) {
+        *self = *self - rhs;
+    }
+}
+
+impl Mul<isize> for GridOffset {
+    type Output = Self;
+
+    fn mul(self, rhs: isize) -> Self::Output {
+        Self(self.0 * rhs, self.1 * rhs)
+    }
+}
+
+// truncates towards zero, like integer division
+impl Div<isize> for GridOffset {
+    type Output = Self;
+
+    fn div(self, rhs: isize) -> Self::Output {
+        Self(self.0 / rhs, self.1 / rhs)
+    }
+}
+
+pub fn parse_char_grid(input: &str) -> Grid<char> {
+    Grid::<char>::parse(input, |x| x)
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Grid<T> {
+    pub cells: Vec<T>,
+    pub rows: usize,
+    pub cols: usize,
+    pub topology: Topology,
+}
+
+impl<T> Grid<T> {
+    // panics where `try_parse` would fail
+    pub fn parse(input: &str, sym: impl Fn(char) -> T) -> Self {
+        Self::try_parse(input, |c| Ok(sym(c))).unwrap_or_else(|err| panic!("{err}"))
+    }
+
+    // One row per non-blank line, trimmed. Stops at the first rejected cell, with its
+    // position filled in.
+    pub fn try_parse(
+        input: &str,
+        sym: impl Fn(char) -> Result<T, UnknownSymbol>,
+    ) -> Result<Self, GridParseError> {
+        let lines: Vec<_> = input
+            .lines()
+            .map(str::trim)
+            .filter(|x| !x.is_empty())
+            .collect();
+        let cols = lines.first().ok_or(GridParseError::Empty)?.chars().count();
+
+        let mut cells = Vec::with_capacity(lines.len() * cols);
+        for (row, line) in lines.iter().enumerate() {
+            let found = line.chars().count();
+            if found != cols {
+                return Err(RaggedRows {
+                    row,
+                    expected: cols,
+                    found,
+                }
+                .into());
+            }
+
+            for (col, c) in line.chars().enumerate() {
+                let at = Some(GridIdx::new(row, col));
+                cells.push(sym(c).map_err(|err| UnknownSymbol { at, ..err })?);
+            }
+        }
+
+        Ok(Self {
+            cells,
+            rows: lines.len(),
+            cols,
+            topology: Topology::Bounded,
+        })
+    }
+
+    pub fn size(&self) -> (usize, usize) {
+        (self.rows, self.cols)
+    }
+
+    pub fn flat_index(&self, index: GridIdx) -> Option<usize> {
+        let index = match self.topology {
+            Topology::Bounded => index,
+            Topology::Wrapping | Topology::Infinite => self.wrap(index)?,
+        };
+        self.bounds()
+            .contains(index)
+            .then(|| index.row() * self.cols + index.col())
+    }
+
+    pub fn grid_idx(&self, index: usize) -> Option<GridIdx> {
+        let range = 0..self.cells.len();
+        if range.contains(&index) {
+            let index = index as isize;
+            let cols = self.cols as isize;
+            let grid_idx = GridIdx(index / cols, index % cols);
+            Some(grid_idx)
+        } else {
+            None
+        }
+    }
+
+    pub fn get(&self, index: GridIdx) -> Option<&T> {
+        self.cells.get(self.flat_index(index)?)
+    }
+
+    pub fn put(&mut self, index: GridIdx, cell: T) -> bool {
+        match self.flat_index(index) {
+            Some(index) => {
+                self.cells[index] = cell;
+                true
+            }
+            None => false,
+        }
+    }
+
+    pub fn position(&self, pred: impl Fn(&T) -> bool) -> Option<GridIdx> {
+        self.grid_idx(self.cells.iter().position(pred)?)
+    }
+
+    // Rows, columns and diagonals cover the stored cells only, whatever the topology.
+    pub fn column(&self, col: usize) -> GridLane<'_, T> {
+        self.stored_lane(GridIdx(0, col as isize), GridOffset(1, 0))
+    }
+
+    pub fn row(&self, row: usize) -> GridLane<'_, T> {
+        self.stored_lane(GridIdx(row as isize, 0), GridOffset(0, 1))
+    }
+
+    pub fn iter_from_with_stride(&self, start: GridIdx, stride: GridOffset) -> GridLane<'_, T> {
+        assert!(!stride.is_zero(), "cannot step a lane by a zero stride");
+        let back = match self.topology {
+            Topology::Bounded => Some(self.stored_len(start, stride)),
+            _ if self.resolve(start).is_none() => Some(0),
+            Topology::Wrapping => Some(self.lap_len(stride)),
+            // never ends
+            Topology::Infinite => None,
+        };
+        GridLane {
+            grid: self,
+            start,
+            stride,
+            front: 0,
+            back,
+        }
+    }
+
+    fn stored_lane(&self, start: GridIdx, stride: GridOffset) -> GridLane<'_, T> {
+        GridLane {
+            grid: self,
+            start,
+            stride,
+            front: 0,
+            back: Some(self.stored_len(start, stride)),
+        }
+    }
+
+    // steps before leaving the stored cells, for a non-zero stride
+    fn stored_len(&self, start: GridIdx, stride: GridOffset) -> usize {
+        if !self.bounds().contains(start) {
+            return 0;
+        }
+
+        // steps before leaving `0..n` along one axis
+        let axis = |pos: isize, step: isize, n: usize| match step {
+            0 => usize::MAX,
+            1.. => ((n as isize - 1 - pos) / step + 1) as usize,
+            _ => (pos / -step + 1) as usize,
+        };
+        axis(start.0, stride.0, self.rows).min(axis(start.1, stride.1, self.cols))
+    }
+
+    // steps before a lane on a non-empty grid returns to its starting cell modulo the size
+    pub(crate) fn lap_len(&self, stride: GridOffset) -> usize {
+        // steps before an axis returns to its starting position
+        let period = |step: isize, n: usize| {
+            let n = n as isize;
+            n / gcd(step.rem_euclid(n), n)
+        };
+        let rows = period(stride.0, self.rows);
+        let cols = period(stride.1, self.cols);
+        (rows / gcd(rows, cols) * cols) as usize
+    }
+
+    pub fn row_lanes(&self) -> impl DoubleEndedIterator<Item = GridLane<'_, T>> {
+        (0..self.rows).map(|row| self.row(row))
+    }
+
+    pub fn column_lanes(&self) -> impl DoubleEndedIterator<Item = GridLane<'_, T>> {
+        (0..self.cols).map(|col| self.column(col))
+    }
+
+    // every down-right diagonal, starting from the bottom left corner
+    pub fn diagonals(&self) -> impl Iterator<Item = GridLane<'_, T>> {
+        let left = (1..self.rows).rev().map(|row| GridIdx::new(row, 0));
+        let top = (0..self.cols).map(|col| GridIdx::new(0, col));
+        left.chain(top)
+            .map(|start| self.stored_lane(start, GridOffset(1, 1)))
+    }
+
+    // every down-left diagonal, starting from the top left corner
+    pub fn anti_diagonals(&self) -> impl Iterator<Item = GridLane<'_, T>> {
+        let top = (0..self.cols).map(|col| GridIdx::new(0, col));
+        let right = (1..self.rows).map(|row| GridIdx::new(row, self.cols.saturating_sub(1)));
+        top.chain(right)
+            .map(|start| self.stored_lane(start, GridOffset(1, -1)))
+    }
+
+    // rows, then columns, then diagonals, then anti-diagonals
+    pub fn all_lanes(&self) -> impl Iterator<Item = GridLane<'_, T>> {
+        self.row_lanes()
+            .chain(self.column_lanes())
+            .chain(self.diagonals())
+            .chain(self.anti_diagonals())
+    }
+
+    pub fn indices(&self) -> impl Iterator<Item = GridIdx> + use<T> {
+        self.bounds().iter()
+    }
+
+    pub fn neighbors(
+        &self,
+        index: GridIdx,
+        connectivity: Connectivity,
+    ) -> impl Iterator<Item = (GridIdx, &T)> {
+        connectivity.offsets().filter_map(move |offset| {
+            let neighbor = self.resolve(index + offset)?;
+            Some((neighbor, self.get(neighbor)?))
+        })
+    }
+}
+
+// Cells from `start` stepping by a non-zero `stride`. A lane ends where it leaves the stored
+// cells, or with `iter_from_with_stride`, after one lap of a wrapping grid. Lanes on an
+// infinite grid never end and cannot be iterated from the back.
+pub struct GridLane<'a, T> {
+    grid: &'a Grid<T>,
+    start: GridIdx,
+    stride: GridOffset,
+    // steps taken from the front, and the step count where the lane ends
+    front: usize,
+    back: Option<usize>,
+}
+
+impl<'a, T> GridLane<'a, T> {
+    fn at(&self, step: usize) -> Option<(GridIdx, &'a T)> {
+        let index = self
+            .grid
+            .resolve(self.start + self.stride * step as isize)?;
+        Some((index, self.grid.get(index)?))
+    }
+
+    pub fn values(self) -> impl DoubleEndedIterator<Item = &'a T> {
+        self.map(|(_, cell)| cell)
+    }
+}
+
+// Gathers the cells of a lane of `char`s, also when reversed or otherwise adapted.
+pub trait CollectString {
+    fn collect_string(self) -> String;
+}
+
+impl<'a, I: Iterator<Item = (GridIdx, &'a char)>> CollectString for I {
+    fn collect_string(self) -> String {
+        self.map(|(_, cell)| cell).collect()
+    }
+}
+
+impl<'a, T> Iterator for GridLane<'a, T> {
+    type Item = (GridIdx, &'a T);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.back.is_some_and(|back| self.front >= back) {
+            return None;
+        }
+
+        let result = self.at(self.front)?;
+        self.front += 1;
+        Some(result)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        match self.back {
+            Some(back) => (back - self.front, Some(back - self.front)),
+            None => (0, None),
+        }
+    }
+}
+
+// Panics on a lane without an end, which has no back to start from.
+impl<T> DoubleEndedIterator for GridLane<'_, T> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        let back = self.back.expect("lane without an end has no back");
+        let back = Some(back).filter(|back| *back > self.front)? - 1;
+        self.back = Some(back);
+        self.at(back)
+    }
+}
+
+fn gcd(a: isize, b: isize) -> isize {
+    if b == 0 { a.abs() } else { gcd(b, a % b) }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const INPUT: &str = "abc\ndef\n";
+
+    #[test]
+    fn test_offset_arithmetic() {
+        let mut offset = GridOffset(4, -6);
+        assert_eq!(-offset, GridOffset(-4, 6));
+        assert_eq!(offset / 2, GridOffset(2, -3));
+        assert_eq!(offset - GridOffset(1, 1), GridOffset(3, -7));
+        offset += GridOffset(1, 1);
+        offset -= GridOffset(0, 2);
+        assert_eq!(offset, GridOffset(5, -7));
+
+        let mut idx = GridIdx(1, 1);
+        idx += GridOffset(2, 0);
+        idx -= GridOffset(0, 3);
+        assert_eq!(idx, GridIdx(3, -2));
+        assert_eq!(<(isize, isize)>::from(idx), (3, -2));
+        assert_eq!(GridIdx::from((3, -2)), idx);
+        assert_eq!(GridOffset::from((0, 1)), Horz::Right.into());
+    }
+
+    #[test]
+    fn test_offset_metrics() {
+        let offset = GridOffset(4, -6);
+        assert_eq!(offset.manhattan(), 10);
+        assert_eq!(offset.chebyshev(), 6);
+        assert_eq!(offset.signum(), GridOffset(1, -1));
+        assert_eq!(offset.gcd_normalize(), GridOffset(2, -3));
+        assert_eq!(GridOffset(0, -5).gcd_normalize(), GridOffset(0, -1));
+        assert_eq!(GridOffset(0, 0).gcd_normalize(), GridOffset(0, 0));
+        assert!(GridOffset(0, 0).is_zero());
+        assert!(!offset.is_zero());
+
+        assert_eq!(GridIdx(1, 2).manhattan(GridIdx(-2, 4)), 5);
+        assert_eq!(GridIdx(1, 2).chebyshev(GridIdx(-2, 4)), 3);
+    }
+
+    #[test]
+    fn test_checked_conversions() {
+        assert_eq!(GridIdx(3, 4).try_row(), Some(3));
+        assert_eq!(GridIdx(-1, 4).try_row(), None);
+        assert_eq!(GridIdx(3, -4).try_col(), None);
+
+        let grid = parse_char_grid(INPUT);
+        assert_eq!(grid.flat_index(GridIdx(-1, 0)), None);
+        assert_eq!(grid.flat_index(GridIdx(1, 2)), Some(5));
+    }
+
+    #[test]
+    fn test_lane_both_ends() {
+        let grid = parse_char_grid(INPUT);
+        assert_eq!(grid.row(1).collect_string(), "def");
+        assert_eq!(grid.row(1).rev().collect_string(), "fed");
+        assert_eq!(grid.column(2).rev().collect_string(), "fc");
+        assert_eq!(grid.row(2).collect_string(), "");
+
+        let mut lane = grid.iter_from_with_stride(GridIdx(0, 2), GridOffset(0, -1));
+        assert_eq!(lane.size_hint(), (3, Some(3)));
+        assert_eq!(lane.next_back(), Some((GridIdx(0, 0), &'a')));
+        assert_eq!(lane.next(), Some((GridIdx(0, 2), &'c')));
+        assert_eq!(lane.next_back(), Some((GridIdx(0, 1), &'b')));
+        assert_eq!(lane.next(), None);
+        assert_eq!(lane.next_back(), None);
+    }
+
+    #[test]
+    fn test_lane_without_end() {
+        let grid = parse_char_grid(INPUT);
+        let infinite = grid.clone().with_topology(Topology::Infinite);
+        let lane = infinite.iter_from_with_stride(GridIdx(0, 1), GridOffset(0, 1));
+        assert_eq!(lane.size_hint(), (0, None));
+        assert_eq!(lane.take(4).collect_string(), "bcab");
+
+        let wrapping = grid.clone().with_topology(Topology::Wrapping);
+        let lane = wrapping.iter_from_with_stride(GridIdx(1, 0), GridOffset(0, 2));
+        assert_eq!(lane.rev().collect_string(), "efd");
+    }
+
+    #[test]
+    #[should_panic(expected = "zero stride")]
+    fn test_zero_stride() {
+        let grid = parse_char_grid(INPUT);
+        grid.iter_from_with_stride(GridIdx(0, 0), GridOffset(0, 0));
+    }
+
+    #[test]
+    #[should_panic(expected = "no back")]
+    fn test_rev_lane_without_end() {
+        let grid = parse_char_grid(INPUT).with_topology(Topology::Infinite);
+        let mut lane = grid.iter_from_with_stride(GridIdx(0, 0), GridOffset(0, 1));
+        lane.next_back();
+    }
+
+    #[test]
+    fn test_diagonals() {
+        let grid = parse_char_grid(INPUT);
+        let diagonals: Vec<_> = grid
+            .diagonals()
+            .map(CollectString::collect_string)
+            .collect();
+        assert_eq!(diagonals, ["d", "ae", "bf", "c"]);
+
+        let anti: Vec<_> = grid
+            .anti_diagonals()
+            .map(CollectString::collect_string)
+            .collect();
+        assert_eq!(anti, ["a", "bd", "ce", "f"]);
+    }
+
+    #[test]
+    fn test_all_lanes() {
+        let grid = parse_char_grid(INPUT);
+        let lanes: Vec<_> = grid
+            .all_lanes()
+            .map(CollectString::collect_string)
+            .collect();
+        assert_eq!(lanes.len(), 2 + 3 + 4 + 4);
+        assert_eq!(lanes[..5], ["abc", "def", "ad", "be", "cf"]);
+    }
+}
@@ -0,0 +1,576 @@
+use std::array;
+use std::error::Error;
+use std::fmt;
+
+use super::*;
+
+// Counterparts of `GridIdx`, `GridOffset`, `GridBounds` and `Grid` for any number of
+// dimensions. Axis 0 varies slowest, so for 2D an index is `[row, col]` and for layered
+// 3D input `[layer, row, col]`.
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct NdIdx<const N: usize>(pub [isize; N]);
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct NdOffset<const N: usize>(pub [isize; N]);
+
+pub type GridIdx3 = NdIdx<3>;
+pub type GridOffset3 = NdOffset<3>;
+pub type Grid3<T> = NdGrid<T, 3>;
+
+impl<const N: usize> NdIdx<N> {
+    pub fn manhattan(self, other: Self) -> usize {
+        (self - other).manhattan()
+    }
+
+    pub fn chebyshev(self, other: Self) -> usize {
+        (self - other).chebyshev()
+    }
+}
+
+impl<const N: usize> NdOffset<N> {
+    pub fn is_zero(self) -> bool {
+        self.0.iter().all(|d| *d == 0)
+    }
+
+    pub fn manhattan(self) -> usize {
+        self.0.iter().map(|d| d.unsigned_abs()).sum()
+    }
+
+    pub fn chebyshev(self) -> usize {
+        self.0.iter().map(|d| d.unsigned_abs()).max().unwrap_or(0)
+    }
+
+    // the 2N unit steps along a single axis
+    pub fn orthogonal() -> impl Iterator<Item = Self> {
+        (0..N).flat_map(|axis| {
+            [-1, 1].map(|d| Self(array::from_fn(|a| if a == axis { d } else { 0 })))
+        })
+    }
+
+    // the 3^N - 1 steps of at most one along every axis
+    pub fn adjacent() -> impl Iterator<Item = Self> {
+        (0..3usize.pow(N as u32))
+            .map(|n| {
+                Self(array::from_fn(|a| {
+                    (n / 3usize.pow((N - 1 - a) as u32) % 3) as isize - 1
+                }))
+            })
+            .filter(|offset| !offset.is_zero())
+    }
+}
+
+// `Orthogonal` is 4 neighbors in 2D and 6 in 3D, `Full` is 8 and 26.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum Neighborhood {
+    Orthogonal,
+    Full,
+}
+
+impl Neighborhood {
+    pub fn offsets<const N: usize>(self) -> Vec<NdOffset<N>> {
+        match self {
+            Neighborhood::Orthogonal => NdOffset::orthogonal().collect(),
+            Neighborhood::Full => NdOffset::adjacent().collect(),
+        }
+    }
+}
+
+impl From<GridIdx> for NdIdx<2> {
+    fn from(value: GridIdx) -> Self {
+        Self([value.0, value.1])
+    }
+}
+
+impl From<NdIdx<2>> for GridIdx {
+    fn from(NdIdx([row, col]): NdIdx<2>) -> Self {
+        Self(row, col)
+    }
+}
+
+impl From<GridOffset> for NdOffset<2> {
+    fn from(value: GridOffset) -> Self {
+        Self([value.0, value.1])
+    }
+}
+
+impl<const N: usize> Add<NdOffset<N>> for NdIdx<N> {
+    type Output = Self;
+
+    fn add(self, rhs: NdOffset<N>) -> Self::Output {
+        Self(array::from_fn(|a| self.0[a] + rhs.0[a]))
+    }
+}
+
+impl<const N: usize> Sub for NdIdx<N> {
+    type Output = NdOffset<N>;
+
+    fn sub(self, rhs: Self) -> Self::Output {
+        NdOffset(array::from_fn(|a| self.0[a] - rhs.0[a]))
+    }
+}
+
+impl<const N: usize> Sub<NdOffset<N>> for NdIdx<N> {
+    type Output = Self;
+
+    fn sub(self, rhs: NdOffset<N>) -> Self::Output {
+        self + -rhs
+    }
+}
+
+impl<const N: usize> AddAssign<NdOffset<N>> for NdIdx<N> {
+    fn add_assign(&mut self, rhs: NdOffset<N>) {
+        *self = *self + rhs;
+    }
+}
+
+impl<const N: usize> Add for NdOffset<N> {
+    type Output = Self;
+
+    fn add(self, rhs: Self) -> Self::Output {
+        Self(array::from_fn(|a| self.0[a] + rhs.0[a]))
+    }
+}
+
+impl<const N: usize> Neg for NdOffset<N> {
+    type Output = Self;
+
+    fn neg(self) -> Self::Output {
+        Self(self.0.map(|d| -d))
+    }
+}
+
+impl<const N: usize> Mul<isize> for NdOffset<N> {
+    type Output = Self;
+
+    fn mul(self, rhs: isize) -> Self::Output {
+        Self(self.0.map(|d| d * rhs))
+    }
+}
+
+// inclusive corners, empty when `max` is below `min` on any axis
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub struct NdBounds<const N: usize> {
+    pub min: NdIdx<N>,
+    pub max: NdIdx<N>,
+}
+
+impl<const N: usize> NdBounds<N> {
+    pub fn new(min: NdIdx<N>, max: NdIdx<N>) -> Self {
+        Self { min, max }
+    }
+
+    pub fn from_dims(dims: [usize; N]) -> Self {
+        Self::new(NdIdx([0; N]), NdIdx(dims.map(|d| d as isize - 1)))
+    }
+
+    pub fn point(index: NdIdx<N>) -> Self {
+        Self::new(index, index)
+    }
+
+    // smallest bounds containing every point, `None` without any
+    pub fn enclosing(points: impl IntoIterator<Item = NdIdx<N>>) -> Option<Self> {
+        let mut points = points.into_iter();
+        let first = Self::point(points.next()?);
+        Some(points.fold(first, Self::extend))
+    }
+
+    pub fn is_empty(&self) -> bool {
+        (0..N).any(|a| self.max.0[a] < self.min.0[a])
+    }
+
+    pub fn dims(&self) -> [usize; N] {
+        array::from_fn(|a| (self.max.0[a] - self.min.0[a] + 1).max(0) as usize)
+    }
+
+    pub fn contains(&self, index: NdIdx<N>) -> bool {
+        (0..N).all(|a| (self.min.0[a]..=self.max.0[a]).contains(&index.0[a]))
+    }
+
+    // grown to include `index`
+    pub fn extend(self, index: NdIdx<N>) -> Self {
+        Self::new(
+            NdIdx(array::from_fn(|a| self.min.0[a].min(index.0[a]))),
+            NdIdx(array::from_fn(|a| self.max.0[a].max(index.0[a]))),
+        )
+    }
+
+    // grown by `by` on every side, e.g. to leave room for a simulation to spread
+    pub fn pad(self, by: isize) -> Self {
+        Self::new(
+            NdIdx(self.min.0.map(|d| d - by)),
+            NdIdx(self.max.0.map(|d| d + by)),
+        )
+    }
+
+    // axis 0 slowest, like row-major order
+    pub fn iter(&self) -> impl Iterator<Item = NdIdx<N>> + use<N> {
+        let Self { min, max } = *self;
+        let first = (!self.is_empty()).then_some(min);
+        std::iter::successors(first, move |idx| {
+            let mut next = *idx;
+            for a in (0..N).rev() {
+                if next.0[a] < max.0[a] {
+                    next.0[a] += 1;
+                    return Some(next);
+                }
+                next.0[a] = min.0[a];
+            }
+            None
+        })
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct NdGrid<T, const N: usize> {
+    pub cells: Vec<T>,
+    pub dims: [usize; N],
+}
+
+impl<T, const N: usize> NdGrid<T, N> {
+    pub fn from_fn(dims: [usize; N], mut f: impl FnMut(NdIdx<N>) -> T) -> Self {
+        let cells = NdBounds::from_dims(dims).iter().map(&mut f).collect();
+        Self { cells, dims }
+    }
+
+    // a 2D grid in the last two axes, with every other axis one cell long
+    pub fn from_plane(grid: Grid<T>) -> Self {
+        assert!(N >= 2, "a plane needs at least two axes");
+        let dims = array::from_fn(|a| match N - a {
+            2 => grid.rows,
+            1 => grid.cols,
+            _ => 1,
+        });
+        Self {
+            cells: grid.cells,
+            dims,
+        }
+    }
+
+    pub fn bounds(&self) -> NdBounds<N> {
+        NdBounds::from_dims(self.dims)
+    }
+
+    pub fn flat_index(&self, index: NdIdx<N>) -> Option<usize> {
+        self.bounds()
+            .contains(index)
+            .then(|| (0..N).fold(0, |flat, a| flat * self.dims[a] + index.0[a] as usize))
+    }
+
+    pub fn nd_idx(&self, mut flat: usize) -> Option<NdIdx<N>> {
+        if flat >= self.cells.len() {
+            return None;
+        }
+
+        let mut index = [0; N];
+        for a in (0..N).rev() {
+            index[a] = (flat % self.dims[a]) as isize;
+            flat /= self.dims[a];
+        }
+        Some(NdIdx(index))
+    }
+
+    pub fn get(&self, index: NdIdx<N>) -> Option<&T> {
+        self.cells.get(self.flat_index(index)?)
+    }
+
+    pub fn put(&mut self, index: NdIdx<N>, cell: T) -> bool {
+        match self.flat_index(index) {
+            Some(flat) => {
+                self.cells[flat] = cell;
+                true
+            }
+            None => false,
+        }
+    }
+
+    pub fn indices(&self) -> impl Iterator<Item = NdIdx<N>> + use<T, N> {
+        self.bounds().iter()
+    }
+
+    pub fn enumerate(&self) -> impl Iterator<Item = (NdIdx<N>, &T)> {
+        self.indices().zip(self.cells.iter())
+    }
+
+    pub fn positions(&self, pred: impl Fn(&T) -> bool) -> impl Iterator<Item = NdIdx<N>> {
+        self.enumerate()
+            .filter(move |(_, cell)| pred(cell))
+            .map(|(idx, _)| idx)
+    }
+
+    pub fn count(&self, pred: impl Fn(&T) -> bool) -> usize {
+        self.cells.iter().filter(|cell| pred(cell)).count()
+    }
+
+    pub fn map<U>(&self, f: impl FnMut(&T) -> U) -> NdGrid<U, N> {
+        NdGrid {
+            cells: self.cells.iter().map(f).collect(),
+            dims: self.dims,
+        }
+    }
+
+    pub fn neighbors(
+        &self,
+        index: NdIdx<N>,
+        neighborhood: Neighborhood,
+    ) -> impl Iterator<Item = (NdIdx<N>, &T)> {
+        neighborhood
+            .offsets()
+            .into_iter()
+            .filter_map(move |offset| {
+                let next = index + offset;
+                Some((next, self.get(next)?))
+            })
+    }
+
+    // cells from `start` stepping by a non-zero `stride` until leaving the grid
+    pub fn lane(
+        &self,
+        start: NdIdx<N>,
+        stride: NdOffset<N>,
+    ) -> impl Iterator<Item = (NdIdx<N>, &T)> {
+        assert!(!stride.is_zero(), "cannot step a lane by a zero stride");
+        std::iter::successors(Some(start), move |idx| Some(*idx + stride))
+            .map_while(|idx| Some((idx, self.get(idx)?)))
+    }
+}
+
+impl<T: Clone, const N: usize> NdGrid<T, N> {
+    pub fn new(dims: [usize; N], fill: T) -> Self {
+        Self {
+            cells: vec![fill; dims.iter().product()],
+            dims,
+        }
+    }
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum LayerError {
+    NoLayers,
+    // a row of `layer` that is not as long as the layer's first
+    Ragged {
+        layer: usize,
+        row: usize,
+        expected: usize,
+        found: usize,
+    },
+    // sizes as `(rows, cols)`
+    Mismatch {
+        layer: usize,
+        expected: (usize, usize),
+        found: (usize, usize),
+    },
+}
+
+impl fmt::Display for LayerError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            LayerError::NoLayers => write!(f, "no layers in input"),
+            LayerError::Ragged {
+                layer,
+                row,
+                expected,
+                found,
+            } => write!(
+                f,
+                "layer {layer} row {row} has {found} cells, expected {expected}"
+            ),
+            LayerError::Mismatch {
+                layer,
+                expected: (rows, cols),
+                found: (found_rows, found_cols),
+            } => write!(
+                f,
+                "layer {layer} is {found_rows}x{found_cols}, expected {rows}x{cols}"
+            ),
+        }
+    }
+}
+
+impl Error for LayerError {}
+
+impl<T> NdGrid<T, 3> {
+    // 2D layers separated by blank lines, all the same size
+    pub fn parse_layers(input: &str, sym: impl Fn(char) -> T) -> Result<Self, LayerError> {
+        let lines: Vec<_> = input.lines().map(str::trim).collect();
+        let layers = lines
+            .split(|line| line.is_empty())
+            .filter(|layer| !layer.is_empty())
+            .enumerate()
+            .map(|(i, layer)| {
+                Grid::try_parse(&layer.join("\n"), |c| Ok(sym(c))).map_err(|err| match err {
+                    GridParseError::Ragged(RaggedRows {
+                        row,
+                        expected,
+                        found,
+                    }) => LayerError::Ragged {
+                        layer: i,
+                        row,
+                        expected,
+                        found,
+                    },
+                    // layers are never blank and every symbol is accepted
+                    _ => unreachable!("{err}"),
+                })
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+
+        let (rows, cols) = layers.first().ok_or(LayerError::NoLayers)?.size();
+        if let Some((layer, found)) = layers
+            .iter()
+            .map(Grid::size)
+            .enumerate()
+            .find(|(_, size)| *size != (rows, cols))
+        {
+            return Err(LayerError::Mismatch {
+                layer,
+                expected: (rows, cols),
+                found,
+            });
+        }
+
+        let dims = [layers.len(), rows, cols];
+        let cells = layers.into_iter().flat_map(|layer| layer.cells).collect();
+        Ok(Self { cells, dims })
+    }
+}
+
+// layers separated by blank lines, the inverse of `parse_layers`
+impl<T: CellSymbol> fmt::Display for NdGrid<T, 3> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let [_, rows, cols] = self.dims;
+        for (i, row) in self.cells.chunks(cols.max(1)).enumerate() {
+            if i > 0 {
+                writeln!(f)?;
+                if i % rows == 0 {
+                    writeln!(f)?;
+                }
+            }
+            for cell in row {
+                write!(f, "{}", cell.symbol())?;
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const LAYERS: &str = "
+        #..
+        .#.
+
+        ...
+        ..#
+    ";
+
+    #[test]
+    fn test_offsets() {
+        assert_eq!(NdOffset::<3>::orthogonal().count(), 6);
+        assert_eq!(NdOffset::<3>::adjacent().count(), 26);
+        assert_eq!(NdOffset::<4>::adjacent().count(), 80);
+        assert!(NdOffset::<3>::adjacent().all(|o| o.chebyshev() == 1));
+
+        let planar: Vec<GridOffset> = Connectivity::Eight.offsets().collect();
+        let nd: Vec<_> = NdOffset::<2>::adjacent().collect();
+        assert!(planar.iter().all(|o| nd.contains(&(*o).into())));
+
+        let a: GridIdx3 = NdIdx([0, 0, 0]);
+        let b = a + NdOffset([1, -2, 3]);
+        assert_eq!((a.manhattan(b), a.chebyshev(b)), (6, 3));
+        assert_eq!(b - NdOffset([1, -2, 3]), a);
+    }
+
+    #[test]
+    fn test_bounds() {
+        let bounds = NdBounds::from_dims([2, 1, 3]);
+        let cells: Vec<_> = bounds.iter().collect();
+        assert_eq!(cells.len(), 6);
+        assert_eq!(cells[3], NdIdx([1, 0, 0]));
+        assert!(NdBounds::from_dims([2, 0, 3]).iter().next().is_none());
+
+        let points = [NdIdx([1, -1, 0, 2]), NdIdx([0, 3, 0, -2])];
+        let bounds = NdBounds::enclosing(points).unwrap().pad(1);
+        assert_eq!(bounds.dims(), [4, 7, 3, 7]);
+        assert!(points.iter().all(|p| bounds.contains(*p)));
+    }
+
+    #[test]
+    fn test_layers() {
+        let grid = Grid3::parse_layers(LAYERS, |c| c).unwrap();
+        assert_eq!(grid.dims, [2, 2, 3]);
+        assert_eq!(grid.get(NdIdx([1, 1, 2])), Some(&'#'));
+        assert_eq!(grid.get(NdIdx([2, 0, 0])), None);
+        assert_eq!(grid.to_string(), "#..\n.#.\n\n...\n..#");
+
+        for (idx, _) in grid.enumerate() {
+            assert_eq!(grid.nd_idx(grid.flat_index(idx).unwrap()), Some(idx));
+        }
+
+        let center = NdIdx([0, 1, 1]);
+        assert_eq!(grid.neighbors(center, Neighborhood::Orthogonal).count(), 4);
+        let full: Vec<_> = grid
+            .neighbors(center, Neighborhood::Full)
+            .filter(|(_, c)| **c == '#')
+            .map(|(idx, _)| idx)
+            .collect();
+        assert_eq!(full, [NdIdx([0, 0, 0]), NdIdx([1, 1, 2])]);
+
+        let lane: String = grid
+            .lane(NdIdx([0, 0, 0]), NdOffset([1, 1, 1]))
+            .map(|(_, c)| c)
+            .collect();
+        assert_eq!(lane, "#.");
+    }
+
+    #[test]
+    fn test_bad_layers() {
+        assert_eq!(
+            Grid3::parse_layers("\n  \n", |c| c),
+            Err(LayerError::NoLayers)
+        );
+
+        let err = Grid3::parse_layers("ab\ncd\n\nab\n\nef\ngh", |c| c).unwrap_err();
+        assert_eq!(
+            err,
+            LayerError::Mismatch {
+                layer: 1,
+                expected: (2, 2),
+                found: (1, 2)
+            }
+        );
+        assert_eq!(err.to_string(), "layer 1 is 1x2, expected 2x2");
+
+        let err = Grid3::parse_layers("abc\nde\n\nfgh\nij", |c| c).unwrap_err();
+        assert_eq!(
+            err,
+            LayerError::Ragged {
+                layer: 0,
+                row: 1,
+                expected: 3,
+                found: 2
+            }
+        );
+        assert_eq!(err.to_string(), "layer 0 row 1 has 2 cells, expected 3");
+    }
+
+    #[test]
+    fn test_plane() {
+        let plane = parse_char_grid("#.\n..");
+        let mut grid = NdGrid::<_, 4>::from_plane(plane);
+        assert_eq!(grid.dims, [1, 1, 2, 2]);
+        assert_eq!(
+            grid.positions(|c| *c == '#').collect::<Vec<_>>(),
+            [NdIdx([0; 4])]
+        );
+
+        assert!(grid.put(NdIdx([0, 0, 1, 1]), '#'));
+        assert!(!grid.put(NdIdx([1, 0, 1, 1]), '#'));
+        assert_eq!(grid.count(|c| *c == '#'), 2);
+        assert_eq!(NdGrid::new([3, 3, 3], 0).cells.len(), 27);
+        let sums = NdGrid::from_fn([2, 2], |NdIdx([r, c])| r + c);
+        assert_eq!(sums.cells, [0, 1, 1, 2]);
+    }
+}
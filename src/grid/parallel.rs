@@ -0,0 +1,61 @@
+use rayon::prelude::*;
+
+use super::*;
+
+// Rayon counterparts of the sequential combinators. Results collect in row-major order.
+impl<T: Sync> Grid<T> {
+    pub fn par_indices(&self) -> impl IndexedParallelIterator<Item = GridIdx> + use<T> {
+        let cols = self.cols;
+        (0..self.cells.len())
+            .into_par_iter()
+            .map(move |i| GridIdx::new(i / cols, i % cols))
+    }
+
+    pub fn par_enumerate(&self) -> impl IndexedParallelIterator<Item = (GridIdx, &T)> {
+        self.par_indices().zip(self.cells.par_iter())
+    }
+
+    pub fn par_map<U: Send>(&self, f: impl Fn(&T) -> U + Sync + Send) -> Grid<U> {
+        Grid {
+            cells: self.cells.par_iter().map(f).collect(),
+            rows: self.rows,
+            cols: self.cols,
+            topology: self.topology,
+        }
+    }
+
+    pub fn par_positions(
+        &self,
+        pred: impl Fn(&T) -> bool + Sync + Send,
+    ) -> impl ParallelIterator<Item = GridIdx> {
+        self.par_enumerate()
+            .filter(move |(_, cell)| pred(cell))
+            .map(|(idx, _)| idx)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_matches_sequential() {
+        let grid = parse_char_grid("ab.\n.ab\nb.a\nab.");
+        assert_eq!(
+            grid.par_indices().collect::<Vec<_>>(),
+            grid.indices().collect::<Vec<_>>()
+        );
+        assert_eq!(
+            grid.par_enumerate().collect::<Vec<_>>(),
+            grid.enumerate().collect::<Vec<_>>()
+        );
+        assert_eq!(
+            grid.par_positions(|c| *c == 'a').collect::<Vec<_>>(),
+            grid.positions(|c| *c == 'a').collect::<Vec<_>>()
+        );
+        assert_eq!(
+            grid.par_map(|c| c.to_ascii_uppercase()),
+            grid.map(|c| c.to_ascii_uppercase())
+        );
+    }
+}
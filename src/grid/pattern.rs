@@ -0,0 +1,128 @@
+use rayon::prelude::*;
+
+use super::*;
+
+// grids with at least this many cells are searched in parallel
+const PARALLEL_THRESHOLD: usize = 64 * 64;
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub struct PatternMatch {
+    // top left corner of the matched window
+    pub anchor: GridIdx,
+    // orientation the pattern was transformed by before matching
+    pub symmetry: Symmetry,
+}
+
+// `None` cells in a pattern are wildcards; anything else must match exactly.
+pub fn parse_pattern(input: &str, wildcard: char) -> Grid<Option<char>> {
+    Grid::parse(input, |c| (c != wildcard).then_some(c))
+}
+
+impl<T: PartialEq> Grid<T> {
+    pub fn matches_at(&self, anchor: GridIdx, pattern: &Grid<Option<T>>) -> bool {
+        let Some(window) = self.view(anchor, pattern.rows, pattern.cols) else {
+            return false;
+        };
+
+        pattern.enumerate().all(|(idx, want)| match want {
+            Some(want) => window.get(idx) == Some(want),
+            None => true,
+        })
+    }
+}
+
+impl<T: PartialEq + Clone + Sync> Grid<T> {
+    pub fn find_pattern(&self, pattern: &Grid<Option<T>>) -> Vec<PatternMatch> {
+        self.find_pattern_with(pattern, [Symmetry::Identity])
+    }
+
+    // Orientations that transform the pattern into one already tried are skipped, so a
+    // symmetric pattern is reported once per anchor rather than once per equivalent symmetry.
+    pub fn find_pattern_with(
+        &self,
+        pattern: &Grid<Option<T>>,
+        symmetries: impl IntoIterator<Item = Symmetry>,
+    ) -> Vec<PatternMatch> {
+        let mut oriented: Vec<(Symmetry, Grid<Option<T>>)> = Vec::new();
+        for symmetry in symmetries {
+            let transformed = pattern.transform(symmetry);
+            if !oriented.iter().any(|(_, seen)| seen == &transformed) {
+                oriented.push((symmetry, transformed));
+            }
+        }
+
+        let matches_at = |anchor: GridIdx| {
+            oriented
+                .iter()
+                .filter(move |(_, pattern)| self.matches_at(anchor, pattern))
+                .map(move |(symmetry, _)| PatternMatch {
+                    anchor,
+                    symmetry: *symmetry,
+                })
+        };
+
+        if self.cells.len() < PARALLEL_THRESHOLD {
+            return self.indices().flat_map(matches_at).collect();
+        }
+
+        self.par_indices().flat_map_iter(matches_at).collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use strum::IntoEnumIterator;
+
+    use super::*;
+
+    const INPUT: &str = "
+        abab
+        baba
+        abab
+    ";
+
+    #[test]
+    fn test_find_pattern() {
+        let grid = parse_char_grid(INPUT);
+        let pattern = parse_pattern("a.\n.a", '.');
+        let anchors: Vec<_> = grid
+            .find_pattern(&pattern)
+            .into_iter()
+            .map(|m| m.anchor)
+            .collect();
+        assert_eq!(anchors, [GridIdx(0, 0), GridIdx(0, 2), GridIdx(1, 1)]);
+
+        let pattern = parse_pattern("aa", '.');
+        assert!(grid.find_pattern(&pattern).is_empty());
+    }
+
+    #[test]
+    fn test_find_pattern_oriented() {
+        let grid = parse_char_grid(INPUT);
+        let pattern = parse_pattern("ab", '.');
+        let found = grid.find_pattern_with(&pattern, Symmetry::iter());
+
+        // "ab" has 4 distinct orientations: ab, ba, and a over b (Rotate90) or b over a
+        let at_origin: Vec<_> = found
+            .iter()
+            .filter(|m| m.anchor == GridIdx(0, 0))
+            .map(|m| m.symmetry)
+            .collect();
+        assert_eq!(at_origin, [Symmetry::Identity, Symmetry::Rotate90]);
+        assert_eq!(found.len(), 17);
+    }
+
+    #[test]
+    fn test_find_pattern_parallel() {
+        let row = "xmas".repeat(20);
+        let input = vec![row; 80].join("\n");
+        let grid = parse_char_grid(&input);
+        assert!(grid.cells.len() >= PARALLEL_THRESHOLD);
+
+        let pattern = parse_pattern("x?a", '?');
+        let found = grid.find_pattern_with(&pattern, Symmetry::iter());
+        // forwards in every block, backwards between neighbouring blocks
+        assert_eq!(found.len(), 80 * (20 + 19));
+        assert!(found.is_sorted_by_key(|m| m.anchor));
+    }
+}
@@ -0,0 +1,179 @@
+use super::*;
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub enum RayEnd {
+    // stopped by the cell at `hit`, `last` is the free cell before it
+    Hit { hit: GridIdx, last: GridIdx },
+    // left the grid, or finished a lap of a wrapping grid, after `last`
+    Exited { last: GridIdx },
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub struct Ray {
+    pub start: GridIdx,
+    pub dir: GridOffset,
+    // moves made from `start` to the last free cell
+    pub steps: usize,
+    pub end: RayEnd,
+    // `(rows, cols)` of a wrapping grid, which the path wraps around
+    wrap: Option<(usize, usize)>,
+}
+
+impl Ray {
+    pub fn last(&self) -> GridIdx {
+        match self.end {
+            RayEnd::Hit { last, .. } | RayEnd::Exited { last } => last,
+        }
+    }
+
+    pub fn hit(&self) -> Option<GridIdx> {
+        match self.end {
+            RayEnd::Hit { hit, .. } => Some(hit),
+            RayEnd::Exited { .. } => None,
+        }
+    }
+
+    // every free cell from `start` to `last`, wrapped like `last`
+    pub fn path(&self) -> impl Iterator<Item = GridIdx> + use<> {
+        let Ray {
+            start, dir, wrap, ..
+        } = *self;
+        (0..=self.steps as isize).map(move |i| {
+            let GridIdx(row, col) = start + dir * i;
+            match wrap {
+                Some((rows, cols)) => {
+                    GridIdx(row.rem_euclid(rows as isize), col.rem_euclid(cols as isize))
+                }
+                None => GridIdx(row, col),
+            }
+        })
+    }
+}
+
+impl<T> Grid<T> {
+    // Steps from `start` in `dir` until a cell matches `stop`. The start cell itself is
+    // never tested. On an infinite grid the cells repeat after one lap, so a ray that has
+    // not been stopped by then exits. Panics on a zero `dir`, or a `start` off the grid.
+    pub fn cast(&self, start: GridIdx, dir: GridOffset, stop: impl Fn(&T) -> bool) -> Ray {
+        assert!(!dir.is_zero(), "cannot cast a ray without a direction");
+        assert!(
+            self.resolve(start).is_some(),
+            "cannot cast a ray from {start:?}, which is off the grid"
+        );
+        let wrap = (self.topology == Topology::Wrapping).then_some((self.rows, self.cols));
+        let limit = match self.topology {
+            Topology::Infinite if !self.cells.is_empty() => self.lap_len(dir),
+            _ => usize::MAX,
+        };
+        let mut last = start;
+        let mut steps = 0;
+        for (idx, cell) in self.iter_from_with_stride(start, dir).take(limit).skip(1) {
+            if stop(cell) {
+                let end = RayEnd::Hit { hit: idx, last };
+                return Ray {
+                    start,
+                    dir,
+                    steps,
+                    end,
+                    wrap,
+                };
+            }
+            last = idx;
+            steps += 1;
+        }
+
+        let end = RayEnd::Exited { last };
+        Ray {
+            start,
+            dir,
+            steps,
+            end,
+            wrap,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const INPUT: &str = "
+        ..#.
+        ....
+        #...
+    ";
+
+    #[test]
+    fn test_cast_hit() {
+        let grid = parse_char_grid(INPUT);
+        let ray = grid.cast(GridIdx(2, 2), GridOffset(-1, 0), |c| *c == '#');
+        assert_eq!(
+            ray.end,
+            RayEnd::Hit {
+                hit: GridIdx(0, 2),
+                last: GridIdx(1, 2)
+            }
+        );
+        assert_eq!(ray.steps, 1);
+        assert_eq!(
+            ray.path().collect::<Vec<_>>(),
+            [GridIdx(2, 2), GridIdx(1, 2)]
+        );
+
+        // blocked straight away
+        let ray = grid.cast(GridIdx(2, 1), GridOffset(0, -1), |c| *c == '#');
+        assert_eq!(
+            (ray.steps, ray.last(), ray.hit()),
+            (0, GridIdx(2, 1), Some(GridIdx(2, 0)))
+        );
+    }
+
+    #[test]
+    fn test_cast_exit() {
+        let grid = parse_char_grid(INPUT);
+        let ray = grid.cast(GridIdx(1, 0), GridOffset(0, 1), |c| *c == '#');
+        assert_eq!(
+            ray.end,
+            RayEnd::Exited {
+                last: GridIdx(1, 3)
+            }
+        );
+        assert_eq!(ray.steps, 3);
+        assert_eq!(ray.hit(), None);
+
+        let wrapping = grid.with_topology(Topology::Wrapping);
+        let ray = wrapping.cast(GridIdx(1, 3), GridOffset(0, 1), |c| *c == '#');
+        assert_eq!(
+            ray.end,
+            RayEnd::Exited {
+                last: GridIdx(1, 2)
+            }
+        );
+        let ray = wrapping.cast(GridIdx(1, 0), GridOffset(1, 0), |c| *c == '#');
+        assert_eq!(ray.hit(), Some(GridIdx(2, 0)));
+        let ray = wrapping.cast(GridIdx(1, 2), GridOffset(1, 0), |c| *c == '#');
+        assert_eq!((ray.hit(), ray.steps), (Some(GridIdx(0, 2)), 1));
+        assert_eq!(
+            ray.path().collect::<Vec<_>>(),
+            [GridIdx(1, 2), GridIdx(2, 2)]
+        );
+
+        let ray = wrapping.cast(GridIdx(1, 2), GridOffset(0, 1), |c| *c == '#');
+        assert_eq!(ray.path().last(), Some(ray.last()));
+        assert_eq!(ray.path().nth(2), Some(GridIdx(1, 0)));
+    }
+
+    #[test]
+    #[should_panic(expected = "without a direction")]
+    fn test_cast_zero_dir() {
+        let grid = parse_char_grid(INPUT);
+        grid.cast(GridIdx(1, 1), GridOffset(0, 0), |c| *c == '#');
+    }
+
+    #[test]
+    #[should_panic(expected = "off the grid")]
+    fn test_cast_off_grid() {
+        let grid = parse_char_grid(INPUT);
+        grid.cast(GridIdx(3, 0), GridOffset(-1, 0), |c| *c == '#');
+    }
+}
@@ -0,0 +1,174 @@
+use super::*;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Region {
+    pub id: usize,
+    // first cell of the region in row-major order
+    pub seed: GridIdx,
+    pub area: usize,
+    // number of unit cell edges bordering another region or the outside
+    pub perimeter: usize,
+    // number of straight runs of border edges
+    pub sides: usize,
+    pub bounds: GridBounds,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Components {
+    // region id of every cell, indexing into `regions`
+    pub labels: Grid<usize>,
+    pub regions: Vec<Region>,
+}
+
+impl Components {
+    pub fn region_of(&self, index: GridIdx) -> Option<&Region> {
+        self.regions.get(*self.labels.get(index)?)
+    }
+}
+
+impl<T> Grid<T> {
+    // Labels cells by flood filling from each unlabeled cell in row-major order. A neighbor
+    // joins the region when `same_region(cell, neighbor)` holds. Perimeter and sides are
+    // always measured on orthogonal cell edges, whatever the connectivity.
+    pub fn components(
+        &self,
+        connectivity: Connectivity,
+        same_region: impl Fn(&T, &T) -> bool,
+    ) -> Components {
+        let mut labels: Vec<Option<usize>> = vec![None; self.cells.len()];
+        let mut regions = Vec::new();
+        let mut stack = Vec::new();
+
+        for (flat, seed) in self.indices().enumerate() {
+            if labels[flat].is_some() {
+                continue;
+            }
+
+            let id = regions.len();
+            let mut region = Region {
+                id,
+                seed,
+                area: 0,
+                perimeter: 0,
+                sides: 0,
+                bounds: GridBounds::point(seed),
+            };
+
+            labels[flat] = Some(id);
+            stack.push(seed);
+            while let Some(idx) = stack.pop() {
+                region.area += 1;
+                region.bounds = region.bounds.extend(idx);
+
+                let cell = self.get(idx).unwrap();
+                for (next, neighbor) in self.neighbors(idx, connectivity) {
+                    let flat = self.flat_index(next).unwrap();
+                    if labels[flat].is_none() && same_region(cell, neighbor) {
+                        labels[flat] = Some(id);
+                        stack.push(next);
+                    }
+                }
+            }
+            regions.push(region);
+        }
+
+        // edges wrap the same way the flood fill did
+        let labels = Grid {
+            cells: labels.into_iter().map(Option::unwrap).collect(),
+            rows: self.rows,
+            cols: self.cols,
+            topology: self.topology,
+        };
+
+        for idx in labels.indices() {
+            let id = labels.get(idx).copied();
+            let inside = |idx: GridIdx| labels.get(idx).copied() == id;
+            for dir in Connectivity::Four.offsets() {
+                if inside(idx + dir) {
+                    continue;
+                }
+
+                let region = &mut regions[id.unwrap()];
+                region.perimeter += 1;
+
+                // only the first edge of each straight run counts as a side
+                let prev = idx + Symmetry::Rotate270.offset(dir);
+                if !inside(prev) || inside(prev + dir) {
+                    region.sides += 1;
+                }
+            }
+        }
+
+        Components { labels, regions }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_components() {
+        let grid = parse_char_grid("AAAA\nBBCD\nBBCC\nEEEC");
+        let components = grid.components(Connectivity::Four, |a, b| a == b);
+        let summary: Vec<_> = components
+            .regions
+            .iter()
+            .map(|r| (*grid.get(r.seed).unwrap(), r.area, r.perimeter, r.sides))
+            .collect();
+        assert_eq!(
+            summary,
+            [
+                ('A', 4, 10, 4),
+                ('B', 4, 8, 4),
+                ('C', 4, 10, 8),
+                ('D', 1, 4, 4),
+                ('E', 3, 8, 4),
+            ]
+        );
+
+        let c = components.region_of(GridIdx(3, 3)).unwrap();
+        assert_eq!(c.bounds, GridBounds::new(GridIdx(1, 2), GridIdx(3, 3)));
+        assert_eq!(components.labels.get(GridIdx(2, 2)), Some(&c.id));
+    }
+
+    #[test]
+    fn test_wrapping() {
+        let grid = parse_char_grid("a.a\n...\na.a").with_topology(Topology::Wrapping);
+        let components = grid.components(Connectivity::Four, |a, b| a == b);
+        let corners = components.region_of(GridIdx(2, 2)).unwrap();
+        assert_eq!(components.labels.get(GridIdx(0, 0)), Some(&corners.id));
+        assert_eq!((corners.area, corners.perimeter, corners.sides), (4, 8, 4));
+    }
+
+    #[test]
+    fn test_sides_with_holes() {
+        let grid = parse_char_grid("AAAAAA\nAAABBA\nAAABBA\nABBAAA\nABBAAA\nAAAAAA");
+        let components = grid.components(Connectivity::Four, |a, b| a == b);
+        let a = components.region_of(GridIdx(0, 0)).unwrap();
+        assert_eq!((a.area, a.sides), (28, 12));
+        assert_eq!(components.regions.len(), 3);
+    }
+
+    #[test]
+    fn test_eight_connected() {
+        let grid = parse_char_grid("#..\n.#.\n..#");
+        let four = grid.components(Connectivity::Four, |a, b| a == b);
+        assert_eq!(four.regions.len(), 5);
+
+        let eight = grid.components(Connectivity::Eight, |a, b| a == b);
+        assert_eq!(eight.regions.len(), 2);
+        let diagonal = eight.region_of(GridIdx(1, 1)).unwrap();
+        assert_eq!((diagonal.area, diagonal.perimeter), (3, 12));
+    }
+
+    #[test]
+    fn test_large_region() {
+        let input = vec![".".repeat(1000); 1000].join("\n");
+        let grid = parse_char_grid(&input);
+        let components = grid.components(Connectivity::Four, |a, b| a == b);
+        assert_eq!(components.regions.len(), 1);
+        assert_eq!(components.regions[0].perimeter, 4000);
+        assert_eq!(components.regions[0].sides, 4);
+    }
+}
@@ -0,0 +1,239 @@
+use std::io::{self, BufRead, Write};
+use std::sync::mpsc::{self, Receiver, RecvTimeoutError};
+use std::thread;
+use std::time::Duration;
+
+use super::*;
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Frame {
+    pub caption: String,
+    pub cells: Grid<char>,
+}
+
+// Snapshots of a simulation, one per call to `record`, for playing back later.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Recorder {
+    pub frames: Vec<Frame>,
+}
+
+impl Recorder {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    // `grid` as text with `marks` drawn over it
+    pub fn record<T: CellSymbol>(
+        &mut self,
+        grid: &Grid<T>,
+        marks: impl IntoIterator<Item = (GridIdx, char)>,
+        caption: impl Into<String>,
+    ) {
+        let mut cells = grid.map(T::symbol);
+        for (idx, mark) in marks {
+            cells.put(idx, mark);
+        }
+        self.frames.push(Frame {
+            caption: caption.into(),
+            cells,
+        });
+    }
+
+    pub fn len(&self) -> usize {
+        self.frames.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.frames.is_empty()
+    }
+
+    // plays on the terminal, reading commands from stdin
+    pub fn play(&self, delay: Duration) -> io::Result<()> {
+        let (send, commands) = mpsc::channel();
+        thread::spawn(move || {
+            for line in io::stdin().lock().lines() {
+                let Ok(line) = line else { break };
+                if let Some(command) = Command::parse(&line)
+                    && send.send(command).is_err()
+                {
+                    break;
+                }
+            }
+        });
+
+        let mut player = Player::new(&self.frames, delay);
+        player.run(&commands, &mut io::stdout().lock())
+    }
+}
+
+// Player controls, one per line of input since the terminal stays in line mode.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum Command {
+    TogglePause,
+    // pause and move one frame
+    Step,
+    Back,
+    Faster,
+    Slower,
+    Quit,
+}
+
+impl Command {
+    pub fn parse(line: &str) -> Option<Command> {
+        match line.trim() {
+            "p" => Some(Command::TogglePause),
+            "" | "n" => Some(Command::Step),
+            "b" => Some(Command::Back),
+            "+" | "f" => Some(Command::Faster),
+            "-" | "s" => Some(Command::Slower),
+            "q" => Some(Command::Quit),
+            _ => None,
+        }
+    }
+}
+
+pub struct Player<'a> {
+    frames: &'a [Frame],
+    delay: Duration,
+    paused: bool,
+    pos: usize,
+}
+
+impl<'a> Player<'a> {
+    const MIN_DELAY: Duration = Duration::from_millis(10);
+    const MAX_DELAY: Duration = Duration::from_secs(5);
+
+    pub fn new(frames: &'a [Frame], delay: Duration) -> Self {
+        Self {
+            frames,
+            delay,
+            paused: false,
+            pos: 0,
+        }
+    }
+
+    pub fn position(&self) -> usize {
+        self.pos
+    }
+
+    // Shows frames until the last one has played, the input closes while paused, or a
+    // `Quit` command arrives.
+    pub fn run(&mut self, commands: &Receiver<Command>, out: &mut impl Write) -> io::Result<()> {
+        if self.frames.is_empty() {
+            return Ok(());
+        }
+
+        loop {
+            self.draw(out)?;
+            let command = if self.paused {
+                match commands.recv() {
+                    Ok(command) => command,
+                    Err(_) => return Ok(()),
+                }
+            } else {
+                match commands.recv_timeout(self.delay) {
+                    Ok(command) => command,
+                    Err(err) => {
+                        // with the input closed, keep the pace without waiting on it
+                        if err == RecvTimeoutError::Disconnected {
+                            thread::sleep(self.delay);
+                        }
+                        if self.pos + 1 == self.frames.len() {
+                            return Ok(());
+                        }
+                        self.pos += 1;
+                        continue;
+                    }
+                }
+            };
+
+            match command {
+                Command::TogglePause => self.paused = !self.paused,
+                Command::Step => {
+                    self.paused = true;
+                    self.pos = (self.pos + 1).min(self.frames.len() - 1);
+                }
+                Command::Back => {
+                    self.paused = true;
+                    self.pos = self.pos.saturating_sub(1);
+                }
+                Command::Faster => self.delay = (self.delay / 2).max(Self::MIN_DELAY),
+                Command::Slower => self.delay = (self.delay * 2).min(Self::MAX_DELAY),
+                Command::Quit => return Ok(()),
+            }
+        }
+    }
+
+    fn draw(&self, out: &mut impl Write) -> io::Result<()> {
+        let frame = &self.frames[self.pos];
+        // clear the screen and home the cursor
+        write!(out, "\x1b[2J\x1b[H")?;
+        writeln!(out, "{}", frame.caption)?;
+        writeln!(out, "{}", frame.cells)?;
+        writeln!(
+            out,
+            "frame {}/{}  {}  {}ms  [enter] step  [b] back  [p] pause  [+/-] speed  [q] quit",
+            self.pos + 1,
+            self.frames.len(),
+            if self.paused { "paused" } else { "playing" },
+            self.delay.as_millis(),
+        )?;
+        out.flush()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn recorder() -> Recorder {
+        let grid = parse_char_grid("...\n...");
+        let mut recorder = Recorder::new();
+        for col in 0..3 {
+            recorder.record(&grid, [(GridIdx(0, col), '@')], format!("col {col}"));
+        }
+        recorder
+    }
+
+    #[test]
+    fn test_record() {
+        let recorder = recorder();
+        assert_eq!(recorder.len(), 3);
+        assert_eq!(recorder.frames[1].caption, "col 1");
+        assert_eq!(recorder.frames[1].cells.to_string(), ".@.\n...");
+    }
+
+    #[test]
+    fn test_play_through() {
+        let recorder = recorder();
+        let (_send, commands) = mpsc::channel();
+        let mut player = Player::new(&recorder.frames, Duration::ZERO);
+        let mut out = Vec::new();
+        player.run(&commands, &mut out).unwrap();
+
+        let out = String::from_utf8(out).unwrap();
+        assert_eq!(out.matches("\x1b[2J").count(), 3);
+        assert!(out.contains("..@\n...\nframe 3/3  playing"));
+        assert_eq!(player.position(), 2);
+    }
+
+    #[test]
+    fn test_controls() {
+        let recorder = recorder();
+        let (send, commands) = mpsc::channel();
+        for line in ["p", "", "", "", "b", "-", "q"] {
+            send.send(Command::parse(line).unwrap()).unwrap();
+        }
+
+        let mut player = Player::new(&recorder.frames, Duration::from_millis(100));
+        let mut out = Vec::new();
+        player.run(&commands, &mut out).unwrap();
+
+        // stepping stops at the last frame
+        assert_eq!(player.position(), 1);
+        let out = String::from_utf8(out).unwrap();
+        assert!(out.contains("frame 3/3  paused  100ms"));
+        assert!(out.contains("frame 2/3  paused  200ms"));
+        assert_eq!(Command::parse("x"), None);
+    }
+}
@@ -0,0 +1,156 @@
+use std::collections::HashSet;
+
+use super::*;
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct WordMatch {
+    pub start: GridIdx,
+    pub dir: GridOffset,
+    pub len: usize,
+}
+
+impl WordMatch {
+    pub fn end(&self) -> GridIdx {
+        self.start + self.dir * (self.len as isize - 1)
+    }
+
+    pub fn cells(&self) -> impl Iterator<Item = GridIdx> + use<> {
+        let WordMatch { start, dir, len } = *self;
+        (0..len as isize).map(move |i| start + dir * i)
+    }
+
+    // the same cells walked from the other end
+    pub fn reversed(&self) -> Self {
+        Self {
+            start: self.end(),
+            dir: -self.dir,
+            len: self.len,
+        }
+    }
+
+    // a match and its reverse share a canonical form, so palindromes found in both
+    // directions can be deduplicated
+    pub fn canonical(&self) -> Self {
+        (*self).min(self.reversed())
+    }
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum Overlap {
+    // every start that spells the needle
+    Allow,
+    // like `str::matches`: along one line and direction, matches never share a cell
+    Disjoint,
+}
+
+impl<T: PartialEq> Grid<T> {
+    pub fn word_at(&self, needle: &[T], start: GridIdx, dir: GridOffset) -> bool {
+        !needle.is_empty()
+            && self
+                .iter_from_with_stride(start, dir)
+                .take(needle.len())
+                .map(|(_, cell)| cell)
+                .eq(needle.iter())
+    }
+
+    // Matches are ordered by start position, then by the order of `dirs`.
+    pub fn find_words(
+        &self,
+        needle: &[T],
+        dirs: impl IntoIterator<Item = GridOffset>,
+        overlap: Overlap,
+    ) -> Vec<WordMatch> {
+        let mut found = Vec::new();
+        for dir in dirs {
+            if dir.is_zero() {
+                continue;
+            }
+
+            let mut matches: Vec<_> = self
+                .indices()
+                .filter(|idx| self.word_at(needle, *idx, dir))
+                .map(|start| WordMatch {
+                    start,
+                    dir,
+                    len: needle.len(),
+                })
+                .collect();
+
+            if overlap == Overlap::Disjoint {
+                // walk each line in the direction of travel so the earliest match wins
+                matches.sort_by_key(|m| m.start.0 * dir.0 + m.start.1 * dir.1);
+                let mut claimed = HashSet::new();
+                matches.retain(|m| {
+                    let free = m.cells().all(|idx| !claimed.contains(&idx));
+                    if free {
+                        claimed.extend(m.cells());
+                    }
+                    free
+                });
+                matches.sort();
+            }
+
+            found.extend(matches);
+        }
+
+        // stable, so ties keep the order of `dirs`
+        found.sort_by_key(|m| m.start);
+        found
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_find_words() {
+        let grid = parse_char_grid("xmas\nmxsa\nasxm");
+        let needle: Vec<char> = "xm".chars().collect();
+        let found = grid.find_words(&needle, Connectivity::Eight.offsets(), Overlap::Allow);
+        let starts: Vec<_> = found.iter().map(|m| (m.start, m.dir)).collect();
+        assert_eq!(
+            starts,
+            [
+                (GridIdx(0, 0), GridOffset(0, 1)),
+                (GridIdx(0, 0), GridOffset(1, 0)),
+                (GridIdx(1, 1), GridOffset(-1, 0)),
+                (GridIdx(1, 1), GridOffset(0, -1)),
+                (GridIdx(2, 2), GridOffset(0, 1)),
+            ]
+        );
+        assert_eq!(found[4].end(), GridIdx(2, 3));
+    }
+
+    #[test]
+    fn test_overlap() {
+        let grid = parse_char_grid("aaaaa");
+        let needle = ['a', 'a'];
+        let dirs = [GridOffset(0, 1)];
+        assert_eq!(grid.find_words(&needle, dirs, Overlap::Allow).len(), 4);
+
+        let found = grid.find_words(&needle, dirs, Overlap::Disjoint);
+        let starts: Vec<_> = found.iter().map(|m| m.start).collect();
+        assert_eq!(starts, [GridIdx(0, 0), GridIdx(0, 2)]);
+
+        // disjoint matches are taken in the direction of travel
+        let found = grid.find_words(&needle, [GridOffset(0, -1)], Overlap::Disjoint);
+        let starts: Vec<_> = found.iter().map(|m| m.start).collect();
+        assert_eq!(starts, [GridIdx(0, 2), GridIdx(0, 4)]);
+    }
+
+    #[test]
+    fn test_palindromes() {
+        let grid = parse_char_grid("abba\nxyzw");
+        let needle: Vec<char> = "abba".chars().collect();
+        let found = grid.find_words(&needle, Connectivity::Four.offsets(), Overlap::Allow);
+        assert_eq!(found.len(), 2);
+
+        let unique: HashSet<_> = found.iter().map(WordMatch::canonical).collect();
+        assert_eq!(unique.len(), 1);
+        assert_eq!(
+            found[0].cells().collect::<Vec<_>>(),
+            found[1].reversed().cells().collect::<Vec<_>>()
+        );
+    }
+}
@@ -0,0 +1,186 @@
+use std::collections::HashMap;
+use std::hash::Hash;
+
+use super::*;
+
+// Cells keyed by position. Bounds are either declared up front or, when undeclared, the
+// extent of the occupied cells.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SparseGrid<T> {
+    pub cells: HashMap<GridIdx, T>,
+    declared: Option<GridBounds>,
+}
+
+impl<T> Default for SparseGrid<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T> SparseGrid<T> {
+    pub fn new() -> Self {
+        Self {
+            cells: HashMap::new(),
+            declared: None,
+        }
+    }
+
+    pub fn with_bounds(bounds: GridBounds) -> Self {
+        Self {
+            cells: HashMap::new(),
+            declared: Some(bounds),
+        }
+    }
+
+    pub fn with_size(rows: usize, cols: usize) -> Self {
+        Self::with_bounds(GridBounds::from_size(rows, cols))
+    }
+
+    // `None` when undeclared and empty
+    pub fn bounds(&self) -> Option<GridBounds> {
+        if self.declared.is_some() {
+            return self.declared;
+        }
+
+        GridBounds::enclosing(self.cells.keys().copied())
+    }
+
+    // O(1) with declared bounds, otherwise a scan of the occupied cells
+    pub fn in_bounds(&self, index: GridIdx) -> bool {
+        self.bounds().is_some_and(|bounds| bounds.contains(index))
+    }
+
+    pub fn len(&self) -> usize {
+        self.cells.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.cells.is_empty()
+    }
+
+    pub fn get(&self, index: GridIdx) -> Option<&T> {
+        self.cells.get(&index)
+    }
+
+    // false when `index` is outside declared bounds
+    pub fn insert(&mut self, index: GridIdx, cell: T) -> bool {
+        if self.declared.is_some() && !self.in_bounds(index) {
+            return false;
+        }
+
+        self.cells.insert(index, cell);
+        true
+    }
+
+    pub fn remove(&mut self, index: GridIdx) -> Option<T> {
+        self.cells.remove(&index)
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = (GridIdx, &T)> {
+        self.cells.iter().map(|(idx, cell)| (*idx, cell))
+    }
+}
+
+impl<T: PartialEq> SparseGrid<T> {
+    // sorted in row-major order
+    pub fn positions_of(&self, value: &T) -> Vec<GridIdx> {
+        let mut positions: Vec<_> = self
+            .iter()
+            .filter(|(_, cell)| *cell == value)
+            .map(|(idx, _)| idx)
+            .collect();
+        positions.sort();
+        positions
+    }
+}
+
+impl<T: Eq + Hash> SparseGrid<T> {
+    // positions of every distinct value, each sorted in row-major order
+    pub fn groups(&self) -> HashMap<&T, Vec<GridIdx>> {
+        let mut groups: HashMap<&T, Vec<GridIdx>> = HashMap::new();
+        for (idx, cell) in self.iter() {
+            groups.entry(cell).or_default().push(idx);
+        }
+        groups.values_mut().for_each(|positions| positions.sort());
+        groups
+    }
+}
+
+impl<T: Clone> SparseGrid<T> {
+    // Dense copy of the bounded area with `default` in empty cells. The dense grid's origin
+    // is the sparse grid's min corner.
+    pub fn to_grid(&self, default: T) -> Grid<T> {
+        let Some(bounds) = self.bounds() else {
+            return Grid::new(0, 0, default);
+        };
+
+        let (rows, cols) = bounds.size();
+        let mut grid = Grid::new(rows, cols, default);
+        for (idx, cell) in self.iter() {
+            grid.put(idx - (bounds.min - GridIdx(0, 0)), cell.clone());
+        }
+        grid
+    }
+}
+
+impl<T: Clone> Grid<T> {
+    // keeps the cells matching `keep`, with bounds declared as the grid's size
+    pub fn to_sparse(&self, keep: impl Fn(&T) -> bool) -> SparseGrid<T> {
+        let mut sparse = SparseGrid::with_size(self.rows, self.cols);
+        for (idx, cell) in self.enumerate() {
+            if keep(cell) {
+                sparse.insert(idx, cell.clone());
+            }
+        }
+        sparse
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_declared_bounds() {
+        let mut sparse = SparseGrid::with_size(2, 3);
+        assert!(sparse.insert(GridIdx(1, 2), 'a'));
+        assert!(!sparse.insert(GridIdx(2, 0), 'b'));
+        assert!(!sparse.insert(GridIdx(0, -1), 'b'));
+        assert_eq!(sparse.len(), 1);
+        assert!(sparse.in_bounds(GridIdx(0, 0)));
+        assert_eq!(sparse.get(GridIdx(1, 2)), Some(&'a'));
+        assert_eq!(sparse.bounds(), Some(GridBounds::from_size(2, 3)));
+    }
+
+    #[test]
+    fn test_dynamic_bounds() {
+        let mut sparse = SparseGrid::new();
+        assert_eq!(sparse.bounds(), None);
+        sparse.insert(GridIdx(-2, 5), 1);
+        sparse.insert(GridIdx(3, -1), 2);
+        sparse.insert(GridIdx(0, 0), 1);
+        assert_eq!(
+            sparse.bounds(),
+            Some(GridBounds::new(GridIdx(-2, -1), GridIdx(3, 5)))
+        );
+        assert_eq!(sparse.positions_of(&1), [GridIdx(-2, 5), GridIdx(0, 0)]);
+
+        let dense = sparse.to_grid(0);
+        assert_eq!(dense.size(), (6, 7));
+        assert_eq!(dense.get(GridIdx(0, 6)), Some(&1));
+        assert_eq!(dense.get(GridIdx(5, 0)), Some(&2));
+        assert_eq!(dense.cells.iter().filter(|c| **c == 0).count(), 39);
+    }
+
+    #[test]
+    fn test_dense_round_trip() {
+        let grid = parse_char_grid("a..\n.b.\n..a");
+        let sparse = grid.to_sparse(|c| *c != '.');
+        assert_eq!(sparse.len(), 3);
+
+        let groups = sparse.groups();
+        assert_eq!(groups[&'a'], [GridIdx(0, 0), GridIdx(2, 2)]);
+        assert_eq!(groups[&'b'], [GridIdx(1, 1)]);
+        assert_eq!(sparse.to_grid('.'), grid);
+    }
+}
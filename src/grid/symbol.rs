@@ -0,0 +1,195 @@
+use std::error::Error;
+use std::fmt;
+
+use super::*;
+
+// One character per cell, both ways, so a grid round-trips through text with a single
+// definition of its alphabet.
+pub trait CellSymbol: Sized {
+    fn from_symbol(symbol: char) -> Result<Self, UnknownSymbol>;
+
+    fn symbol(&self) -> char;
+}
+
+impl CellSymbol for char {
+    fn from_symbol(symbol: char) -> Result<Self, UnknownSymbol> {
+        Ok(symbol)
+    }
+
+    fn symbol(&self) -> char {
+        *self
+    }
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct UnknownSymbol {
+    pub symbol: char,
+    // filled in when parsing a whole grid
+    pub at: Option<GridIdx>,
+}
+
+impl UnknownSymbol {
+    pub fn new(symbol: char) -> Self {
+        Self { symbol, at: None }
+    }
+}
+
+impl fmt::Display for UnknownSymbol {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "unknown cell symbol {:?}", self.symbol)?;
+        if let Some(GridIdx(row, col)) = self.at {
+            write!(f, " at row {row}, column {col}")?;
+        }
+        Ok(())
+    }
+}
+
+impl Error for UnknownSymbol {}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum GridParseError {
+    // no non-blank lines
+    Empty,
+    Ragged(RaggedRows),
+    UnknownSymbol(UnknownSymbol),
+}
+
+impl fmt::Display for GridParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            GridParseError::Empty => write!(f, "no rows in grid input"),
+            GridParseError::Ragged(err) => err.fmt(f),
+            GridParseError::UnknownSymbol(err) => err.fmt(f),
+        }
+    }
+}
+
+impl Error for GridParseError {}
+
+impl From<RaggedRows> for GridParseError {
+    fn from(value: RaggedRows) -> Self {
+        GridParseError::Ragged(value)
+    }
+}
+
+impl From<UnknownSymbol> for GridParseError {
+    fn from(value: UnknownSymbol) -> Self {
+        GridParseError::UnknownSymbol(value)
+    }
+}
+
+impl<T: CellSymbol> Grid<T> {
+    pub fn parse_symbols(input: &str) -> Result<Self, GridParseError> {
+        Self::try_parse(input, T::from_symbol)
+    }
+}
+
+// one line per row, without a trailing newline
+impl<T: CellSymbol> fmt::Display for Grid<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.cols == 0 {
+            return Ok(());
+        }
+        for (i, row) in self.cells.chunks(self.cols).enumerate() {
+            if i > 0 {
+                writeln!(f)?;
+            }
+            for cell in row {
+                write!(f, "{}", cell.symbol())?;
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[derive(Debug, Copy, Clone, PartialEq)]
+    enum Tile {
+        Wall,
+        Floor,
+    }
+
+    impl CellSymbol for Tile {
+        fn from_symbol(symbol: char) -> Result<Self, UnknownSymbol> {
+            match symbol {
+                '#' => Ok(Tile::Wall),
+                '.' => Ok(Tile::Floor),
+                c => Err(UnknownSymbol::new(c)),
+            }
+        }
+
+        fn symbol(&self) -> char {
+            match self {
+                Tile::Wall => '#',
+                Tile::Floor => '.',
+            }
+        }
+    }
+
+    #[test]
+    fn test_round_trip() {
+        let input = "#.#\n..#";
+        let grid = Grid::<Tile>::parse_symbols(input).unwrap();
+        assert_eq!(grid.get(GridIdx(1, 0)), Some(&Tile::Floor));
+        assert_eq!(grid.to_string(), input);
+        assert_eq!(parse_char_grid(input).to_string(), input);
+    }
+
+    #[test]
+    fn test_unknown_symbol() {
+        let err = Grid::<Tile>::parse_symbols("#.\n.x").unwrap_err();
+        let GridParseError::UnknownSymbol(unknown) = err else {
+            panic!("expected an unknown symbol, got {err:?}");
+        };
+        assert_eq!(unknown.symbol, 'x');
+        assert_eq!(unknown.at, Some(GridIdx(1, 1)));
+        assert_eq!(
+            err.to_string(),
+            "unknown cell symbol 'x' at row 1, column 1"
+        );
+    }
+
+    #[test]
+    fn test_malformed_input() {
+        assert_eq!(
+            Grid::<char>::parse_symbols(" \n\n"),
+            Err(GridParseError::Empty)
+        );
+        assert_eq!(
+            Grid::<char>::parse_symbols("ab\nc"),
+            Err(GridParseError::Ragged(RaggedRows {
+                row: 1,
+                expected: 2,
+                found: 1
+            }))
+        );
+    }
+
+    #[derive(Debug, Copy, Clone, PartialEq, GridCell)]
+    enum Derived {
+        #[symbol('#')]
+        Wall,
+        #[symbol('.')]
+        Floor,
+        #[symbol('~')]
+        Water,
+    }
+
+    #[test]
+    fn test_derive() {
+        assert_eq!(
+            Derived::ALL,
+            [Derived::Wall, Derived::Floor, Derived::Water]
+        );
+        assert_eq!(Derived::from_symbol('~'), Ok(Derived::Water));
+        assert_eq!(Derived::from_symbol('x'), Err(UnknownSymbol::new('x')));
+
+        let input = "#~.\n~~#";
+        let grid = Grid::<Derived>::parse_symbols(input).unwrap();
+        assert_eq!(grid.get(GridIdx(1, 0)), Some(&Derived::Water));
+        assert_eq!(grid.to_string(), input);
+    }
+}
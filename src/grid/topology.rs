@@ -0,0 +1,125 @@
+use super::*;
+
+// How indices outside `0..rows` x `0..cols` behave.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Default)]
+pub enum Topology {
+    // out of range indices have no cell
+    #[default]
+    Bounded,
+    // indices wrap around the edges onto the grid (a torus)
+    Wrapping,
+    // the grid tiles the plane; indices keep their position in the plane
+    Infinite,
+}
+
+impl<T> Grid<T> {
+    pub fn with_topology(mut self, topology: Topology) -> Self {
+        self.topology = topology;
+        self
+    }
+
+    // `index` modulo the grid size, `None` for an empty grid
+    pub fn wrap(&self, index: GridIdx) -> Option<GridIdx> {
+        if self.rows == 0 || self.cols == 0 {
+            return None;
+        }
+
+        let rows = self.rows as isize;
+        let cols = self.cols as isize;
+        Some(GridIdx(index.0.rem_euclid(rows), index.1.rem_euclid(cols)))
+    }
+
+    // The index reported for `index` by lanes and neighbor iteration: wrapped back onto the
+    // grid for `Wrapping`, unchanged for `Infinite`, and `None` when it has no cell.
+    pub fn resolve(&self, index: GridIdx) -> Option<GridIdx> {
+        match self.topology {
+            Topology::Bounded => self.flat_index(index).map(|_| index),
+            Topology::Wrapping => self.wrap(index),
+            Topology::Infinite => self.wrap(index).map(|_| index),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const INPUT: &str = "abc\ndef\n";
+
+    #[test]
+    fn test_bounded() {
+        let grid = parse_char_grid(INPUT);
+        assert_eq!(grid.get(GridIdx(-1, 0)), None);
+        assert_eq!(grid.row(0).count(), 3);
+        assert_eq!(grid.neighbors(GridIdx(0, 0), Connectivity::Four).count(), 2);
+    }
+
+    #[test]
+    fn test_wrapping() {
+        let mut grid = parse_char_grid(INPUT).with_topology(Topology::Wrapping);
+        assert_eq!(grid.get(GridIdx(-1, 0)), Some(&'d'));
+        assert_eq!(grid.get(GridIdx(2, 5)), Some(&'c'));
+
+        // lanes stop after one lap, starting anywhere
+        let lane: String = grid
+            .iter_from_with_stride(GridIdx(1, 2), GridOffset(0, 1))
+            .map(|(_, c)| c)
+            .collect();
+        assert_eq!(lane, "fde");
+        let diagonal: Vec<_> = grid
+            .iter_from_with_stride(GridIdx(0, 0), GridOffset(1, 1))
+            .map(|(idx, _)| idx)
+            .collect();
+        assert_eq!(diagonal.len(), 6);
+        assert_eq!(diagonal[2], GridIdx(0, 2));
+
+        let neighbors: Vec<_> = grid
+            .neighbors(GridIdx(0, 0), Connectivity::Four)
+            .map(|(idx, _)| idx)
+            .collect();
+        assert_eq!(
+            neighbors,
+            [GridIdx(1, 0), GridIdx(0, 2), GridIdx(0, 1), GridIdx(1, 0)]
+        );
+
+        assert!(grid.put(GridIdx(-2, -1), 'z'));
+        assert_eq!(grid.get(GridIdx(0, 2)), Some(&'z'));
+    }
+
+    #[test]
+    fn test_infinite() {
+        let grid = parse_char_grid(INPUT).with_topology(Topology::Infinite);
+        assert_eq!(grid.get(GridIdx(-1, 0)), Some(&'d'));
+
+        let lane: Vec<_> = grid
+            .iter_from_with_stride(GridIdx(0, 0), GridOffset(0, 1))
+            .take(5)
+            .collect();
+        assert_eq!(lane[4], (GridIdx(0, 4), &'b'));
+
+        let neighbors: Vec<_> = grid
+            .neighbors(GridIdx(0, 0), Connectivity::Four)
+            .map(|(idx, _)| idx)
+            .collect();
+        assert_eq!(
+            neighbors,
+            [GridIdx(-1, 0), GridIdx(0, -1), GridIdx(0, 1), GridIdx(1, 0)]
+        );
+    }
+
+    #[test]
+    fn test_stored_lanes() {
+        // rows, columns and text cover the stored cells whatever the topology
+        for topology in [Topology::Wrapping, Topology::Infinite] {
+            let grid = parse_char_grid(INPUT).with_topology(topology);
+            assert_eq!(grid.to_string(), "abc\ndef");
+            assert_eq!(grid.row(0).count(), 3);
+            assert_eq!(grid.column(2).rev().collect_string(), "fc");
+            assert_eq!(grid.row_lanes().count(), 2);
+            assert_eq!(grid.all_lanes().map(Iterator::count).sum::<usize>(), 24);
+
+            let ray = grid.cast(GridIdx(0, 0), GridOffset(0, 1), |c| *c == 'z');
+            assert_eq!(ray.hit(), None);
+        }
+    }
+}
@@ -0,0 +1,187 @@
+use itertools::Itertools;
+use strum::IntoEnumIterator;
+
+use super::*;
+
+/// The 8 symmetries of a rectangle (the dihedral group D4). Rotations are clockwise.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash, strum::EnumIter)]
+pub enum Symmetry {
+    Identity,
+    Rotate90,
+    Rotate180,
+    Rotate270,
+    FlipHorizontal,
+    FlipVertical,
+    Transpose,
+    AntiTranspose,
+}
+
+impl Symmetry {
+    pub fn inverse(self) -> Self {
+        match self {
+            Self::Rotate90 => Self::Rotate270,
+            Self::Rotate270 => Self::Rotate90,
+            other => other,
+        }
+    }
+
+    pub fn swaps_axes(self) -> bool {
+        matches!(
+            self,
+            Self::Rotate90 | Self::Rotate270 | Self::Transpose | Self::AntiTranspose
+        )
+    }
+
+    // size of the transformed grid given the (rows, cols) of the source
+    pub fn size(self, (rows, cols): (usize, usize)) -> (usize, usize) {
+        if self.swaps_axes() {
+            (cols, rows)
+        } else {
+            (rows, cols)
+        }
+    }
+
+    // where a displacement points after the transform
+    pub fn offset(self, offset: GridOffset) -> GridOffset {
+        let GridOffset(r, c) = offset;
+        match self {
+            Self::Identity => GridOffset(r, c),
+            Self::Rotate90 => GridOffset(c, -r),
+            Self::Rotate180 => GridOffset(-r, -c),
+            Self::Rotate270 => GridOffset(-c, r),
+            Self::FlipHorizontal => GridOffset(r, -c),
+            Self::FlipVertical => GridOffset(-r, c),
+            Self::Transpose => GridOffset(c, r),
+            Self::AntiTranspose => GridOffset(-c, -r),
+        }
+    }
+
+    // where a cell of a grid with the given (rows, cols) lands after the transform
+    pub fn idx(self, idx: GridIdx, (rows, cols): (usize, usize)) -> GridIdx {
+        let (rows, cols) = (rows as isize, cols as isize);
+        let GridIdx(r, c) = idx;
+        match self {
+            Self::Identity => GridIdx(r, c),
+            Self::Rotate90 => GridIdx(c, rows - 1 - r),
+            Self::Rotate180 => GridIdx(rows - 1 - r, cols - 1 - c),
+            Self::Rotate270 => GridIdx(cols - 1 - c, r),
+            Self::FlipHorizontal => GridIdx(r, cols - 1 - c),
+            Self::FlipVertical => GridIdx(rows - 1 - r, c),
+            Self::Transpose => GridIdx(c, r),
+            Self::AntiTranspose => GridIdx(cols - 1 - c, rows - 1 - r),
+        }
+    }
+}
+
+impl<T: Clone> Grid<T> {
+    pub fn transform(&self, sym: Symmetry) -> Self {
+        let (rows, cols) = sym.size(self.size());
+        let inverse = sym.inverse();
+        let cells = (0..rows as isize)
+            .cartesian_product(0..cols as isize)
+            .map(|(r, c)| inverse.idx(GridIdx(r, c), (rows, cols)))
+            .map(|idx| self.get(idx).expect("symmetry maps onto the grid").clone())
+            .collect();
+        Self {
+            cells,
+            rows,
+            cols,
+            topology: self.topology,
+        }
+    }
+
+    pub fn transpose(&self) -> Self {
+        self.transform(Symmetry::Transpose)
+    }
+
+    pub fn rotate_cw(&self) -> Self {
+        self.transform(Symmetry::Rotate90)
+    }
+
+    pub fn rotate_ccw(&self) -> Self {
+        self.transform(Symmetry::Rotate270)
+    }
+
+    pub fn rotate_180(&self) -> Self {
+        self.transform(Symmetry::Rotate180)
+    }
+
+    pub fn flip_horizontal(&self) -> Self {
+        self.transform(Symmetry::FlipHorizontal)
+    }
+
+    pub fn flip_vertical(&self) -> Self {
+        self.transform(Symmetry::FlipVertical)
+    }
+
+    pub fn symmetries(&self) -> impl Iterator<Item = (Symmetry, Self)> + '_ {
+        Symmetry::iter().map(|sym| (sym, self.transform(sym)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const INPUT: &str = "abc\ndef\n";
+
+    fn render(grid: &Grid<char>) -> Vec<String> {
+        (0..grid.rows)
+            .map(|r| grid.row(r).map(|(_, c)| *c).collect())
+            .collect()
+    }
+
+    #[test]
+    fn test_rotations() {
+        let grid = parse_char_grid(INPUT);
+        let cw = grid.rotate_cw();
+        assert_eq!(cw.size(), (3, 2));
+        assert_eq!(render(&cw), ["da", "eb", "fc"]);
+
+        let ccw = grid.rotate_ccw();
+        assert_eq!(ccw.size(), (3, 2));
+        assert_eq!(render(&ccw), ["cf", "be", "ad"]);
+
+        assert_eq!(render(&grid.rotate_180()), ["fed", "cba"]);
+        assert_eq!(cw.rotate_cw(), grid.rotate_180());
+        assert_eq!(cw.rotate_ccw(), grid);
+    }
+
+    #[test]
+    fn test_flips() {
+        let grid = parse_char_grid(INPUT);
+        assert_eq!(render(&grid.flip_horizontal()), ["cba", "fed"]);
+        assert_eq!(render(&grid.flip_vertical()), ["def", "abc"]);
+        assert_eq!(render(&grid.transpose()), ["ad", "be", "cf"]);
+        assert_eq!(
+            render(&grid.transform(Symmetry::AntiTranspose)),
+            ["fc", "eb", "da"]
+        );
+    }
+
+    #[test]
+    fn test_symmetries() {
+        let grid = parse_char_grid(INPUT);
+        let all: Vec<_> = grid.symmetries().collect();
+        assert_eq!(all.len(), 8);
+        assert!(all.iter().map(|(_, g)| render(g)).all_unique());
+
+        for (sym, transformed) in all {
+            assert_eq!(transformed.transform(sym.inverse()), grid);
+            for idx in grid.indices() {
+                let moved = sym.idx(idx, grid.size());
+                assert_eq!(transformed.get(moved), grid.get(idx));
+            }
+        }
+    }
+
+    #[test]
+    fn test_offsets_follow_cells() {
+        let grid = parse_char_grid(INPUT);
+        let (a, b) = (GridIdx(0, 0), GridIdx(1, 2));
+        for sym in Symmetry::iter() {
+            let moved = sym.idx(b, grid.size()) - sym.idx(a, grid.size());
+            assert_eq!(moved, sym.offset(b - a));
+        }
+    }
+}
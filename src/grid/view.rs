@@ -0,0 +1,179 @@
+use itertools::Itertools;
+
+use super::*;
+
+// A borrowed rectangle of a grid. All indices are local to the view's origin.
+#[derive(Debug)]
+pub struct GridView<'a, T> {
+    grid: &'a Grid<T>,
+    origin: GridIdx,
+    rows: usize,
+    cols: usize,
+}
+
+impl<T> Clone for GridView<'_, T> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<T> Copy for GridView<'_, T> {}
+
+impl<T> Grid<T> {
+    pub fn view(&self, origin: GridIdx, rows: usize, cols: usize) -> Option<GridView<'_, T>> {
+        self.as_view().view(origin, rows, cols)
+    }
+
+    pub fn as_view(&self) -> GridView<'_, T> {
+        GridView {
+            grid: self,
+            origin: GridIdx(0, 0),
+            rows: self.rows,
+            cols: self.cols,
+        }
+    }
+
+    // every `rows` x `cols` view, row-major by origin; panics on a zero size like
+    // `slice::windows`
+    pub fn windows(&self, rows: usize, cols: usize) -> impl Iterator<Item = GridView<'_, T>> {
+        assert!(rows > 0 && cols > 0, "window size must be non-zero");
+        let row_starts = 0..(self.rows + 1).saturating_sub(rows);
+        let col_starts = 0..(self.cols + 1).saturating_sub(cols);
+        row_starts
+            .cartesian_product(col_starts)
+            .filter_map(move |(r, c)| self.view(GridIdx::new(r, c), rows, cols))
+    }
+}
+
+impl<'a, T> GridView<'a, T> {
+    pub fn size(&self) -> (usize, usize) {
+        (self.rows, self.cols)
+    }
+
+    // position of the view's (0, 0) in the underlying grid
+    pub fn origin(&self) -> GridIdx {
+        self.origin
+    }
+
+    pub fn contains(&self, index: GridIdx) -> bool {
+        GridBounds::from_size(self.rows, self.cols).contains(index)
+    }
+
+    pub fn to_grid_idx(&self, index: GridIdx) -> Option<GridIdx> {
+        self.contains(index)
+            .then(|| self.origin + (index - GridIdx(0, 0)))
+    }
+
+    pub fn get(&self, index: GridIdx) -> Option<&'a T> {
+        self.grid.get(self.to_grid_idx(index)?)
+    }
+
+    pub fn column(&self, col: usize) -> ViewLane<'a, T> {
+        self.iter_from_with_stride(GridIdx(0, col as isize), GridOffset(1, 0))
+    }
+
+    pub fn row(&self, row: usize) -> ViewLane<'a, T> {
+        self.iter_from_with_stride(GridIdx(row as isize, 0), GridOffset(0, 1))
+    }
+
+    pub fn iter_from_with_stride(&self, start: GridIdx, stride: GridOffset) -> ViewLane<'a, T> {
+        assert!(!stride.is_zero(), "cannot step a lane by a zero stride");
+        ViewLane {
+            view: *self,
+            state: start,
+            stride,
+        }
+    }
+
+    pub fn indices(&self) -> impl Iterator<Item = GridIdx> + use<T> {
+        GridBounds::from_size(self.rows, self.cols).iter()
+    }
+
+    pub fn view(&self, origin: GridIdx, rows: usize, cols: usize) -> Option<GridView<'a, T>> {
+        let far = origin + GridOffset(rows as isize, cols as isize);
+        let fits = origin.0 >= 0
+            && origin.1 >= 0
+            && far.0 <= self.rows as isize
+            && far.1 <= self.cols as isize;
+        fits.then_some(GridView {
+            grid: self.grid,
+            origin: self.origin + (origin - GridIdx(0, 0)),
+            rows,
+            cols,
+        })
+    }
+}
+
+impl<T: Clone> GridView<'_, T> {
+    pub fn to_grid(&self) -> Grid<T> {
+        Grid::from_fn(self.rows, self.cols, |idx| self.get(idx).unwrap().clone())
+    }
+}
+
+pub struct ViewLane<'a, T> {
+    view: GridView<'a, T>,
+    state: GridIdx,
+    stride: GridOffset,
+}
+
+impl<'a, T> Iterator for ViewLane<'a, T> {
+    type Item = (GridIdx, &'a T);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let cell = self.view.get(self.state)?;
+        let result = (self.state, cell);
+        self.state += self.stride;
+        Some(result)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const INPUT: &str = "abcd\nefgh\nijkl\n";
+
+    #[test]
+    fn test_view() {
+        let grid = parse_char_grid(INPUT);
+        let view = grid.view(GridIdx(1, 1), 2, 3).unwrap();
+        assert_eq!(view.size(), (2, 3));
+        assert_eq!(view.get(GridIdx(0, 0)), Some(&'f'));
+        assert_eq!(view.get(GridIdx(1, 2)), Some(&'l'));
+        assert_eq!(view.get(GridIdx(2, 0)), None);
+        assert_eq!(view.get(GridIdx(0, -1)), None);
+
+        let row: String = view.row(1).map(|(_, c)| c).collect();
+        assert_eq!(row, "jkl");
+        let col: String = view.column(0).map(|(_, c)| c).collect();
+        assert_eq!(col, "fj");
+        assert_eq!(view.indices().count(), 6);
+
+        assert!(grid.view(GridIdx(2, 2), 2, 2).is_none());
+        assert!(grid.view(GridIdx(-1, 0), 1, 1).is_none());
+        assert_eq!(grid.as_view().to_grid(), grid);
+
+        let sub = view.view(GridIdx(1, 1), 1, 2).unwrap();
+        assert_eq!(sub.origin(), GridIdx(2, 2));
+        assert_eq!(sub.to_grid(), parse_char_grid("kl"));
+    }
+
+    #[test]
+    fn test_windows() {
+        let grid = parse_char_grid(INPUT);
+        let windows: Vec<_> = grid.windows(2, 2).collect();
+        assert_eq!(windows.len(), 6);
+        assert_eq!(windows[0].origin(), GridIdx(0, 0));
+        assert_eq!(windows[5].origin(), GridIdx(1, 2));
+        assert_eq!(windows[5].get(GridIdx(1, 1)), Some(&'l'));
+
+        assert_eq!(grid.windows(3, 4).count(), 1);
+        assert_eq!(grid.windows(4, 1).count(), 0);
+    }
+
+    #[test]
+    #[should_panic(expected = "window size must be non-zero")]
+    fn test_empty_windows() {
+        let _ = parse_char_grid(INPUT).windows(2, 0);
+    }
+}
@@ -0,0 +1,386 @@
+use core::ops::{Add, AddAssign, Mul, Neg, Sub, SubAssign};
+use std::error::Error;
+use std::fmt;
+use std::str::FromStr;
+
+use strum::{EnumIter, IntoEnumIterator};
+
+// Hexagonal grids in axial coordinates `(q, r)`. The implied third cube coordinate is
+// `s = -q - r`. Axial coordinates work for both layouts; only the direction names differ:
+// pointy-topped hexes have east and west neighbors, flat-topped ones north and south.
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct HexIdx(pub isize, pub isize);
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct HexOffset(pub isize, pub isize);
+
+// cube coordinates, always with `q + r + s == 0`
+#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Cube(pub isize, pub isize, pub isize);
+
+impl HexOffset {
+    // counter-clockwise, starting from +q
+    pub const DIRECTIONS: [HexOffset; 6] = [
+        HexOffset(1, 0),
+        HexOffset(1, -1),
+        HexOffset(0, -1),
+        HexOffset(-1, 0),
+        HexOffset(-1, 1),
+        HexOffset(0, 1),
+    ];
+
+    pub fn q(self) -> isize {
+        self.0
+    }
+
+    pub fn r(self) -> isize {
+        self.1
+    }
+
+    pub fn s(self) -> isize {
+        -self.0 - self.1
+    }
+
+    pub fn is_zero(self) -> bool {
+        self == Self(0, 0)
+    }
+
+    // number of single steps it takes
+    pub fn distance(self) -> usize {
+        let Cube(q, r, s) = self.into();
+        (q.unsigned_abs() + r.unsigned_abs() + s.unsigned_abs()) / 2
+    }
+
+    // by 60 degrees
+    pub fn rotate_cw(self) -> Self {
+        Self(-self.1, -self.s())
+    }
+
+    pub fn rotate_ccw(self) -> Self {
+        Self(-self.s(), -self.0)
+    }
+}
+
+impl HexIdx {
+    pub fn q(self) -> isize {
+        self.0
+    }
+
+    pub fn r(self) -> isize {
+        self.1
+    }
+
+    pub fn s(self) -> isize {
+        -self.0 - self.1
+    }
+
+    pub fn distance(self, other: Self) -> usize {
+        (self - other).distance()
+    }
+
+    pub fn neighbors(self) -> impl Iterator<Item = HexIdx> {
+        HexOffset::DIRECTIONS.into_iter().map(move |dir| self + dir)
+    }
+
+    // the 6 * radius cells at exactly `radius` steps, just the center for radius 0
+    pub fn ring(self, radius: usize) -> impl Iterator<Item = HexIdx> {
+        let start = self + HexOffset::DIRECTIONS[4] * radius as isize;
+        let steps = HexOffset::DIRECTIONS
+            .into_iter()
+            .flat_map(move |dir| std::iter::repeat_n(dir, radius));
+        let ring = steps.scan(start, |idx, dir| {
+            let here = *idx;
+            *idx += dir;
+            Some(here)
+        });
+        std::iter::once(self)
+            .filter(move |_| radius == 0)
+            .chain(ring)
+    }
+
+    // every cell within `radius` steps, ring by ring from the center
+    pub fn spiral(self, radius: usize) -> impl Iterator<Item = HexIdx> {
+        (0..=radius).flat_map(move |r| self.ring(r))
+    }
+}
+
+impl From<HexOffset> for Cube {
+    fn from(value: HexOffset) -> Self {
+        Cube(value.0, value.1, value.s())
+    }
+}
+
+impl From<HexIdx> for Cube {
+    fn from(value: HexIdx) -> Self {
+        Cube(value.0, value.1, value.s())
+    }
+}
+
+impl From<Cube> for HexIdx {
+    fn from(Cube(q, r, s): Cube) -> Self {
+        debug_assert_eq!(q + r + s, 0, "cube coordinates must sum to zero");
+        HexIdx(q, r)
+    }
+}
+
+impl From<(isize, isize)> for HexIdx {
+    fn from((q, r): (isize, isize)) -> Self {
+        Self(q, r)
+    }
+}
+
+impl From<(isize, isize)> for HexOffset {
+    fn from((q, r): (isize, isize)) -> Self {
+        Self(q, r)
+    }
+}
+
+impl Add for HexOffset {
+    type Output = Self;
+
+    fn add(self, rhs: Self) -> Self::Output {
+        Self(self.0 + rhs.0, self.1 + rhs.1)
+    }
+}
+
+impl Sub for HexOffset {
+    type Output = Self;
+
+    fn sub(self, rhs: Self) -> Self::Output {
+        Self(self.0 - rhs.0, self.1 - rhs.1)
+    }
+}
+
+impl Neg for HexOffset {
+    type Output = Self;
+
+    fn neg(self) -> Self::Output {
+        Self(-self.0, -self.1)
+    }
+}
+
+impl Mul<isize> for HexOffset {
+    type Output = Self;
+
+    fn mul(self, rhs: isize) -> Self::Output {
+        Self(self.0 * rhs, self.1 * rhs)
+    }
+}
+
+impl AddAssign for HexOffset {
+    fn add_assign(&mut self, rhs: Self) {
+        *self = *self + rhs;
+    }
+}
+
+impl Add<HexOffset> for HexIdx {
+    type Output = Self;
+
+    fn add(self, rhs: HexOffset) -> Self::Output {
+        Self(self.0 + rhs.0, self.1 + rhs.1)
+    }
+}
+
+impl Sub for HexIdx {
+    type Output = HexOffset;
+
+    fn sub(self, rhs: Self) -> Self::Output {
+        HexOffset(self.0 - rhs.0, self.1 - rhs.1)
+    }
+}
+
+impl Sub<HexOffset> for HexIdx {
+    type Output = Self;
+
+    fn sub(self, rhs: HexOffset) -> Self::Output {
+        Self(self.0 - rhs.0, self.1 - rhs.1)
+    }
+}
+
+impl AddAssign<HexOffset> for HexIdx {
+    fn add_assign(&mut self, rhs: HexOffset) {
+        *self = *self + rhs;
+    }
+}
+
+impl SubAssign<HexOffset> for HexIdx {
+    fn sub_assign(&mut self, rhs: HexOffset) {
+        *self = *self - rhs;
+    }
+}
+
+// neighbors of a pointy-topped hex
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash, EnumIter)]
+pub enum PointyDir {
+    E,
+    NE,
+    NW,
+    W,
+    SW,
+    SE,
+}
+
+impl From<PointyDir> for HexOffset {
+    fn from(value: PointyDir) -> Self {
+        HexOffset::DIRECTIONS[value as usize]
+    }
+}
+
+// neighbors of a flat-topped hex
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash, EnumIter)]
+pub enum FlatDir {
+    SE,
+    NE,
+    N,
+    NW,
+    SW,
+    S,
+}
+
+impl From<FlatDir> for HexOffset {
+    fn from(value: FlatDir) -> Self {
+        HexOffset::DIRECTIONS[value as usize]
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct UnknownDirection(pub String);
+
+impl fmt::Display for UnknownDirection {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "unknown hex direction {:?}", self.0)
+    }
+}
+
+impl Error for UnknownDirection {}
+
+// case-insensitive lookup by variant name
+fn parse_dir<D: IntoEnumIterator + fmt::Debug>(s: &str) -> Result<D, UnknownDirection> {
+    D::iter()
+        .find(|dir| format!("{dir:?}").eq_ignore_ascii_case(s))
+        .ok_or_else(|| UnknownDirection(s.to_string()))
+}
+
+impl FromStr for PointyDir {
+    type Err = UnknownDirection;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        parse_dir(s)
+    }
+}
+
+impl FromStr for FlatDir {
+    type Err = UnknownDirection;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        parse_dir(s)
+    }
+}
+
+// Directions separated by commas or whitespace, or run together as in `esenee`: an `n` or
+// `s` followed by `e` or `w` is read as one direction.
+pub fn parse_dirs<D: FromStr<Err = UnknownDirection>>(
+    input: &str,
+) -> Result<Vec<D>, UnknownDirection> {
+    let chars: Vec<char> = input.chars().collect();
+    let mut dirs = Vec::new();
+    let mut i = 0;
+    while i < chars.len() {
+        if chars[i] == ',' || chars[i].is_whitespace() {
+            i += 1;
+            continue;
+        }
+
+        let pair = matches!(chars[i].to_ascii_lowercase(), 'n' | 's')
+            && chars
+                .get(i + 1)
+                .is_some_and(|c| matches!(c.to_ascii_lowercase(), 'e' | 'w'));
+        let len = if pair { 2 } else { 1 };
+        let token: String = chars[i..i + len].iter().collect();
+        dirs.push(token.parse()?);
+        i += len;
+    }
+    Ok(dirs)
+}
+
+// where a walk along `dirs` from `start` ends up
+pub fn walk<D: Into<HexOffset>>(start: HexIdx, dirs: impl IntoIterator<Item = D>) -> HexIdx {
+    dirs.into_iter().fold(start, |idx, dir| idx + dir.into())
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashSet;
+
+    use super::*;
+
+    const ORIGIN: HexIdx = HexIdx(0, 0);
+
+    #[test]
+    fn test_arithmetic() {
+        let a = HexIdx(2, -1);
+        let b = HexIdx(-1, 3);
+        assert_eq!(a.distance(b), 4);
+        assert_eq!(a + (b - a), b);
+        assert_eq!(Cube::from(a), Cube(2, -1, -1));
+        assert_eq!(HexIdx::from(Cube(2, -1, -1)), a);
+
+        for dir in HexOffset::DIRECTIONS {
+            assert_eq!(dir.distance(), 1);
+            assert_eq!(dir.rotate_cw().rotate_ccw(), dir);
+            assert_eq!(-dir, dir.rotate_cw().rotate_cw().rotate_cw());
+        }
+        assert_eq!(
+            HexOffset::DIRECTIONS[0].rotate_ccw(),
+            HexOffset::DIRECTIONS[1]
+        );
+    }
+
+    #[test]
+    fn test_rings() {
+        assert_eq!(ORIGIN.ring(0).collect::<Vec<_>>(), [ORIGIN]);
+        for radius in 1..4 {
+            let ring: HashSet<_> = ORIGIN.ring(radius).collect();
+            assert_eq!(ring.len(), 6 * radius);
+            assert!(ring.iter().all(|idx| ORIGIN.distance(*idx) == radius));
+        }
+
+        let spiral: Vec<_> = HexIdx(5, -2).spiral(2).collect();
+        assert_eq!(spiral.len(), 19);
+        assert_eq!(spiral[0], HexIdx(5, -2));
+        assert_eq!(spiral.iter().collect::<HashSet<_>>().len(), 19);
+    }
+
+    #[test]
+    fn test_parse_flat() {
+        // distances from AoC 2017 day 11
+        let cases = [
+            ("ne,ne,ne", 3),
+            ("ne,ne,sw,sw", 0),
+            ("ne,ne,s,s", 2),
+            ("se,sw,se,sw,sw", 3),
+        ];
+        for (input, distance) in cases {
+            let dirs = parse_dirs::<FlatDir>(input).unwrap();
+            assert_eq!(walk(ORIGIN, dirs).distance(ORIGIN), distance);
+        }
+        assert_eq!(
+            parse_dirs::<FlatDir>("n,e"),
+            Err(UnknownDirection("e".to_string()))
+        );
+    }
+
+    #[test]
+    fn test_parse_pointy() {
+        let dirs = parse_dirs::<PointyDir>("e se w").unwrap();
+        assert_eq!(dirs, [PointyDir::E, PointyDir::SE, PointyDir::W]);
+
+        // run together, from AoC 2020 day 24
+        let dirs = parse_dirs::<PointyDir>("nwwswee").unwrap();
+        assert_eq!(walk(ORIGIN, dirs), ORIGIN);
+        let dirs = parse_dirs::<PointyDir>("esew").unwrap();
+        assert_eq!(walk(ORIGIN, dirs), ORIGIN + PointyDir::SE.into());
+        assert!(parse_dirs::<PointyDir>("n").is_err());
+    }
+}
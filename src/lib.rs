@@ -1,3 +1,8 @@
+// lets derive macros name this crate as `::aoc2024` from inside it too
+extern crate self as aoc2024;
+
 pub mod day;
+pub mod graph;
 pub mod grid;
+pub mod hex;
 pub mod parse;